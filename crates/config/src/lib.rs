@@ -1,36 +1,425 @@
+pub mod locale;
 pub mod schema;
 pub mod watcher;
 
-pub use schema::{CardConfig, DashConfig, DashboardConfig, ThemeConfig};
+pub use schema::{CardConfig, DashConfig, DashboardConfig, PaletteEntry, ReduceMotion, ThemeConfig};
 pub use watcher::ConfigWatcher;
 
+use bar_core::{BarError, Result};
 use std::path::{Path, PathBuf};
 
-/// Load configuration from a TOML file.  Returns `DashConfig::default()` if
-/// the file doesn't exist so the dashboard always has sensible defaults.
-pub fn load(path: impl AsRef<Path>) -> Result<DashConfig, String> {
+/// Load configuration from a TOML file (or JSON, for a `.json` path).
+/// A missing file counts as an empty one, so the dashboard always has
+/// sensible defaults.  Loading never writes anything; see [`write_default`].
+pub fn load(path: impl AsRef<Path>) -> Result<DashConfig> {
+    load_with_sources(path).map(|(cfg, _)| cfg)
+}
+
+/// Like [`load`], but also returns every path that contributed to the result —
+/// the main config first, followed by any resolved includes and the `conf.d/`
+/// directory — so callers can hand the whole set to [`ConfigWatcher`].
+///
+/// Two include forms are supported, both resolved relative to the main file:
+/// - `include = ["widgets.toml", ...]` at the top level: each file is merged
+///   in order, and the main file's own keys win over included ones.
+/// - `theme = "catppuccin.toml"`: the file's `[theme]` table (or the whole
+///   file, if it has none) is used as the theme section.
+///
+/// After that, `*.toml` files in a `conf.d/` directory next to the main file
+/// are applied in lexical order (`10-vpn.toml` before `20-backup.toml`).
+/// A drop-in's `dashboard.items` are appended to the existing list, or
+/// replace it when the file sets `replace = true`; any other key overrides.
+///
+/// Missing or malformed includes and drop-ins are skipped with a warning.
+/// Last, `auto_locale = true` fills in locale-based defaults for keys the
+/// config leaves unset (see [`locale`]).
+pub fn load_with_sources(path: impl AsRef<Path>) -> Result<(DashConfig, Vec<PathBuf>)> {
+    let path = path.as_ref();
+    let mut root = if path.exists() { read_table(path)? } else { toml::Table::new() };
+    let mut sources = vec![path.to_path_buf()];
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut merged = toml::Table::new();
+    for include in take_includes(&mut root) {
+        let inc_path = base_dir.join(&include);
+        match read_table(&inc_path) {
+            Ok(mut table) => {
+                if table.remove("include").is_some() {
+                    tracing::warn!(
+                        "Nested includes in '{}' are not supported; ignoring them.",
+                        inc_path.display()
+                    );
+                }
+                merge_tables(&mut merged, table);
+                sources.push(inc_path);
+            }
+            Err(e) => tracing::warn!("Skipping include: {e}"),
+        }
+    }
+
+    if let Some(toml::Value::String(theme_file)) = root.get("theme").cloned() {
+        let theme_path = base_dir.join(&theme_file);
+        match read_table(&theme_path) {
+            Ok(mut table) => {
+                let theme = match table.remove("theme") {
+                    Some(toml::Value::Table(t)) => t,
+                    _ => table,
+                };
+                root.insert("theme".to_string(), toml::Value::Table(theme));
+                sources.push(theme_path);
+            }
+            Err(e) => {
+                tracing::warn!("Skipping theme file: {e}");
+                root.remove("theme");
+            }
+        }
+    }
+
+    merge_tables(&mut merged, root);
+    apply_drop_ins(&mut merged, &base_dir.join("conf.d"), &mut sources);
+    locale::apply(&mut merged);
+
+    // Includes and drop-ins are merged by now, so a schema error can't be
+    // pinned to a line; it's reported against the main file.
+    Ok((into_config(merged, path)?, sources))
+}
+
+/// Load only the file at `path`, without includes, the theme file, drop-ins
+/// or locale defaults — what a tool that writes the config back should edit,
+/// so values merged in from elsewhere aren't copied into the main file.
+/// Returns `DashConfig::default()` if the file doesn't exist.
+pub fn load_raw(path: impl AsRef<Path>) -> Result<DashConfig> {
     let path = path.as_ref();
     if !path.exists() {
-        tracing::warn!(
-            "Config file not found at '{}'; using defaults.",
-            path.display()
-        );
         return Ok(DashConfig::default());
     }
+    let mut root = read_table(path)?;
+    root.remove("include");
+    if matches!(root.get("theme"), Some(toml::Value::String(_))) {
+        root.remove("theme");
+    }
+    into_config(root, path)
+}
+
+/// Deserialise a merged table, reporting schema errors against `path`.
+fn into_config(table: toml::Table, path: &Path) -> Result<DashConfig> {
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| BarError::ConfigParse {
+            path:     path.to_path_buf(),
+            location: None,
+            message:  e.message().to_string(),
+        })
+}
+
+/// Apply every `*.toml` in `dir` on top of `merged`, in file-name order.
+/// A drop-in that fails to parse, or that would make the config invalid, is
+/// skipped so it can't take the main config down with it.
+///
+/// `dir` is added to `sources` even when it doesn't exist yet, so creating
+/// it later is noticed by the watcher.
+fn apply_drop_ins(merged: &mut toml::Table, dir: &Path, sources: &mut Vec<PathBuf>) {
+    sources.push(dir.to_path_buf());
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+
+    for file in files {
+        let mut table = match read_table(&file) {
+            Ok(t) => t,
+            Err(e) => {
+                tracing::warn!("Skipping drop-in: {e}");
+                continue;
+            }
+        };
+        if table.remove("include").is_some() {
+            tracing::warn!("Includes in drop-in '{}' are not supported; ignoring them.", file.display());
+        }
+        let replace = matches!(table.remove("replace"), Some(toml::Value::Boolean(true)));
+
+        let mut candidate = merged.clone();
+        if !replace {
+            append_items(&candidate, &mut table);
+        }
+        merge_tables(&mut candidate, table);
+
+        match toml::Value::Table(candidate.clone()).try_into::<DashConfig>() {
+            Ok(_) => *merged = candidate,
+            Err(e) => tracing::warn!("Skipping drop-in '{}': {e}", file.display()),
+        }
+    }
+}
+
+/// Prefix the drop-in's `dashboard.items` with the items already in `base`, so
+/// the following merge appends rather than replaces them.
+fn append_items(base: &toml::Table, drop_in: &mut toml::Table) {
+    let existing = base
+        .get("dashboard")
+        .and_then(|d| d.get("items"))
+        .and_then(toml::Value::as_array);
+    let incoming = drop_in
+        .get_mut("dashboard")
+        .and_then(toml::Value::as_table_mut)
+        .and_then(|d| d.get_mut("items"))
+        .and_then(toml::Value::as_array_mut);
+    if let (Some(existing), Some(incoming)) = (existing, incoming) {
+        let mut items = existing.clone();
+        items.append(incoming);
+        *incoming = items;
+    }
+}
+
+/// Write `cfg` to `path`, creating parent directories — the starting point
+/// for a first run.  Never overwrites: an existing file makes this fail with
+/// `AlreadyExists`.
+pub fn write_default(path: &Path, cfg: &DashConfig) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let raw = if is_json(path) {
+        serde_json::to_string_pretty(cfg)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+    } else {
+        toml::to_string_pretty(cfg)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(raw.as_bytes())
+}
 
+/// Read and parse a single config file into a raw table: JSON for a `.json`
+/// file, TOML otherwise.  JSON goes through the same table so includes,
+/// drop-ins and merging work the same for both.
+fn read_table(path: &Path) -> Result<toml::Table> {
     let raw = std::fs::read_to_string(path)
-        .map_err(|e| format!("cannot read '{}': {e}", path.display()))?;
+        .map_err(|source| BarError::ConfigRead { path: path.to_path_buf(), source })?;
+
+    if is_json(path) {
+        serde_json::from_str(&raw).map_err(|e| {
+            // serde_json appends the position to its message; it's kept
+            // separately here.
+            let full   = e.to_string();
+            let suffix = format!(" at line {} column {}", e.line(), e.column());
+            BarError::ConfigParse {
+                path:     path.to_path_buf(),
+                location: (e.line() > 0).then(|| (e.line(), e.column())),
+                message:  full.strip_suffix(&suffix).unwrap_or(&full).to_string(),
+            }
+        })
+    } else {
+        toml::from_str(&raw).map_err(|e: toml::de::Error| BarError::ConfigParse {
+            path:     path.to_path_buf(),
+            location: e.span().map(|span| line_column(&raw, span.start)),
+            message:  e.message().to_string(),
+        })
+    }
+}
 
-    toml::from_str(&raw).map_err(|e| format!("TOML parse error: {e}"))
+/// 1-based line and column of byte offset `at` in `raw`.
+fn line_column(raw: &str, at: usize) -> (usize, usize) {
+    let before = raw.get(..at).unwrap_or(raw);
+    let line   = before.matches('\n').count() + 1;
+    let col    = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, col)
 }
 
-/// Return the default config path, honouring `$XDG_CONFIG_HOME`.
+/// Whether `path` names a JSON config (by extension).
+pub fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Remove the top-level `include` key and return its entries.
+fn take_includes(root: &mut toml::Table) -> Vec<String> {
+    match root.remove("include") {
+        Some(toml::Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|v| match v {
+                toml::Value::String(s) => Some(s),
+                other => {
+                    tracing::warn!("Ignoring non-string include entry: {other}");
+                    None
+                }
+            })
+            .collect(),
+        Some(toml::Value::String(s)) => vec![s],
+        Some(other) => {
+            tracing::warn!("`include` must be a list of file names, got: {other}");
+            Vec::new()
+        }
+        None => Vec::new(),
+    }
+}
+
+/// Deep-merge `overlay` into `base`: nested tables merge key by key, any
+/// other value in `overlay` replaces the one in `base`.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Return the config path: `$BAR_CONFIG` if set, otherwise `bar/bar.toml`
+/// under `$XDG_CONFIG_HOME` (default `~/.config`) — or `bar/bar.json` when
+/// only that exists.  The dashboard and the editor both resolve it here, so
+/// they always agree on the file.
 pub fn default_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("BAR_CONFIG").filter(|p| !p.is_empty()) {
+        return PathBuf::from(path);
+    }
     let base = std::env::var("XDG_CONFIG_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| {
             let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
             PathBuf::from(home).join(".config")
         });
-    base.join("bar").join("bar.toml")
+    let dir       = base.join("bar");
+    let toml_path = dir.join("bar.toml");
+    let json_path = dir.join("bar.json");
+    if !toml_path.exists() && json_path.exists() { json_path } else { toml_path }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Tests that change the process environment hold this, so they don't
+    /// see each other's variables.
+    static ENV: Mutex<()> = Mutex::new(());
+
+    /// Run `f` with `vars` set (or removed, for `None`), then restore them.
+    /// Shared with the other modules' tests.
+    pub(crate) fn with_env<T>(vars: &[(&str, Option<&Path>)], f: impl FnOnce() -> T) -> T {
+        let _guard = ENV.lock().unwrap_or_else(|e| e.into_inner());
+        let saved: Vec<_> = vars.iter().map(|(k, _)| (*k, std::env::var_os(k))).collect();
+        for (key, value) in vars {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None    => std::env::remove_var(key),
+            }
+        }
+        let out = f();
+        for (key, value) in saved {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None    => std::env::remove_var(key),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn default_path_prefers_bar_config() {
+        let path = with_env(
+            &[
+                ("BAR_CONFIG", Some(Path::new("/tmp/custom.toml"))),
+                ("XDG_CONFIG_HOME", Some(Path::new("/xdg"))),
+                ("HOME", Some(Path::new("/home/u"))),
+            ],
+            default_path,
+        );
+        assert_eq!(path, PathBuf::from("/tmp/custom.toml"));
+    }
+
+    #[test]
+    fn default_path_uses_xdg_then_home() {
+        let xdg = with_env(
+            &[
+                ("BAR_CONFIG", Some(Path::new(""))),
+                ("XDG_CONFIG_HOME", Some(Path::new("/xdg"))),
+                ("HOME", Some(Path::new("/home/u"))),
+            ],
+            default_path,
+        );
+        assert_eq!(xdg, PathBuf::from("/xdg/bar/bar.toml"));
+
+        let home = with_env(
+            &[("BAR_CONFIG", None), ("XDG_CONFIG_HOME", None), ("HOME", Some(Path::new("/home/u")))],
+            default_path,
+        );
+        assert_eq!(home, PathBuf::from("/home/u/.config/bar/bar.toml"));
+    }
+
+    #[test]
+    fn default_path_falls_back_to_json_only_when_toml_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let bar = dir.path().join("bar");
+        std::fs::create_dir(&bar).unwrap();
+        let vars = [("BAR_CONFIG", None), ("XDG_CONFIG_HOME", Some(dir.path()))];
+
+        assert_eq!(with_env(&vars, default_path), bar.join("bar.toml"));
+        std::fs::write(bar.join("bar.json"), "{}").unwrap();
+        assert_eq!(with_env(&vars, default_path), bar.join("bar.json"));
+        std::fs::write(bar.join("bar.toml"), "").unwrap();
+        assert_eq!(with_env(&vars, default_path), bar.join("bar.toml"));
+    }
+
+    #[test]
+    fn missing_file_loads_defaults_without_writing() {
+        let dir  = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bar.toml");
+
+        assert_eq!(load(&path).unwrap(), DashConfig::default());
+        assert!(!path.exists());
+
+        write_default(&path, &DashConfig::default()).unwrap();
+        assert_eq!(load(&path).unwrap(), DashConfig::default());
+        assert!(write_default(&path, &DashConfig::default()).is_err());
+    }
+
+    #[test]
+    fn missing_conf_d_is_still_a_source() {
+        let dir  = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bar.toml");
+        std::fs::write(&path, "[dashboard]\ncolumns = 3\n").unwrap();
+
+        let (cfg, sources) = load_with_sources(&path).unwrap();
+        assert_eq!(cfg.dashboard.columns, 3);
+        assert_eq!(sources, vec![path, dir.path().join("conf.d")]);
+    }
+
+    #[test]
+    fn drop_in_items_are_appended() {
+        let dir  = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bar.toml");
+        std::fs::write(&path, "[[dashboard.items]]\nkind = \"clock\"\n").unwrap();
+        std::fs::create_dir(dir.path().join("conf.d")).unwrap();
+        std::fs::write(dir.path().join("conf.d/10-vpn.toml"), "[[dashboard.items]]\nkind = \"network\"\n").unwrap();
+
+        let cfg = load(&path).unwrap();
+        let kinds: Vec<&str> = cfg.dashboard.items.iter().map(|c| c.kind.as_str()).collect();
+        assert_eq!(kinds, ["clock", "network"]);
+
+        let raw = load_raw(&path).unwrap();
+        let kinds: Vec<&str> = raw.dashboard.items.iter().map(|c| c.kind.as_str()).collect();
+        assert_eq!(kinds, ["clock"]);
+    }
+
+    #[test]
+    fn raw_load_skips_includes_and_theme_file() {
+        let dir  = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bar.toml");
+        std::fs::write(&path, "include = [\"widgets.toml\"]\ntheme = \"dark.toml\"\n").unwrap();
+        std::fs::write(dir.path().join("widgets.toml"), "[dashboard]\ncolumns = 4\n").unwrap();
+        std::fs::write(dir.path().join("dark.toml"), "[theme]\nbackground = \"#000000\"\n").unwrap();
+
+        let raw = load_raw(&path).unwrap();
+        assert_eq!(raw, DashConfig::default());
+        let full = load(&path).unwrap();
+        assert_eq!(full.dashboard.columns, 4);
+        assert_eq!(full.theme.background, "#000000");
+    }
 }
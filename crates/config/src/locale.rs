@@ -0,0 +1,137 @@
+/// Territories that quote temperatures in Fahrenheit.
+const FAHRENHEIT: [&str; 8] = ["US", "LR", "BS", "BZ", "KY", "PW", "FM", "MH"];
+
+/// Territories where the 12-hour clock is the everyday one.
+const TWELVE_HOUR: [&str; 14] = [
+    "US", "CA", "AU", "NZ", "IN", "PH", "PK", "BD", "EG", "SA", "MY", "CO", "SV", "HN",
+];
+
+/// The defaults a locale implies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocaleDefaults {
+    pub fahrenheit:  bool,
+    pub twelve_hour: bool,
+}
+
+impl LocaleDefaults {
+    /// Value for `theme.temperature_unit`.
+    pub fn temperature_unit(&self) -> &'static str {
+        if self.fahrenheit { "f" } else { "c" }
+    }
+
+    /// Value for `theme.clock_format`.
+    pub fn clock_format(&self) -> &'static str {
+        if self.twelve_hour { "%I:%M %p" } else { "%H:%M" }
+    }
+}
+
+/// Defaults for a locale name such as `en_US.UTF-8` or `de_DE@euro`, or
+/// `None` for `C`/`POSIX` and names without a territory.
+pub fn defaults_for(locale: &str) -> Option<LocaleDefaults> {
+    let name = locale.split(['.', '@']).next().unwrap_or("");
+    let (_, territory) = name.split_once('_')?;
+    let territory = territory.to_ascii_uppercase();
+    Some(LocaleDefaults {
+        fahrenheit:  FAHRENHEIT.contains(&territory.as_str()),
+        twelve_hour: TWELVE_HOUR.contains(&territory.as_str()),
+    })
+}
+
+/// Defaults for the session's time locale: `$LC_ALL`, then `$LC_TIME`, then
+/// `$LANG`, the first one that is set winning as in POSIX.
+pub fn from_env() -> Option<LocaleDefaults> {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .and_then(|v| defaults_for(&v))
+}
+
+/// With `auto_locale = true` in the merged config, fill in
+/// `theme.temperature_unit` and `theme.clock_format` from the locale —
+/// only where the config doesn't set them itself.
+pub(crate) fn apply(merged: &mut toml::Table) {
+    if !matches!(merged.get("auto_locale"), Some(toml::Value::Boolean(true))) {
+        return;
+    }
+    let Some(defaults) = from_env() else { return };
+    let theme = merged
+        .entry("theme")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    let Some(theme) = theme.as_table_mut() else { return };
+    theme
+        .entry("temperature_unit")
+        .or_insert_with(|| defaults.temperature_unit().into());
+    theme
+        .entry("clock_format")
+        .or_insert_with(|| defaults.clock_format().into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::with_env;
+    use std::path::Path;
+
+    const UNSET: [(&str, Option<&Path>); 3] = [("LC_ALL", None), ("LC_TIME", None), ("LANG", None)];
+
+    fn locale_env<'a>(vars: &[(&'a str, &'a str)]) -> Vec<(&'a str, Option<&'a Path>)> {
+        let mut env = UNSET.to_vec();
+        for &(key, value) in vars {
+            env.retain(|&(k, _)| k != key);
+            env.push((key, Some(Path::new(value))));
+        }
+        env
+    }
+
+    #[test]
+    fn defaults_for_common_locale_names() {
+        let us = defaults_for("en_US.UTF-8").unwrap();
+        assert_eq!((us.temperature_unit(), us.clock_format()), ("f", "%I:%M %p"));
+
+        let de = defaults_for("de_DE@euro").unwrap();
+        assert_eq!((de.temperature_unit(), de.clock_format()), ("c", "%H:%M"));
+
+        let gb = defaults_for("en_gb.utf8").unwrap();
+        assert_eq!(gb, LocaleDefaults { fahrenheit: false, twelve_hour: false });
+        assert!(defaults_for("en_IN").unwrap().twelve_hour);
+
+        assert_eq!(defaults_for("C"), None);
+        assert_eq!(defaults_for("POSIX"), None);
+        assert_eq!(defaults_for("C.UTF-8"), None);
+        assert_eq!(defaults_for(""), None);
+    }
+
+    #[test]
+    fn lc_all_wins_over_lc_time_and_lang() {
+        let env = locale_env(&[("LC_ALL", "de_DE.UTF-8"), ("LC_TIME", "en_US.UTF-8"), ("LANG", "en_US.UTF-8")]);
+        assert_eq!(with_env(&env, from_env), defaults_for("de_DE"));
+
+        let env = locale_env(&[("LC_TIME", "de_DE.UTF-8"), ("LANG", "en_US.UTF-8")]);
+        assert_eq!(with_env(&env, from_env), defaults_for("de_DE"));
+
+        // An empty variable doesn't count as set.
+        let env = locale_env(&[("LC_ALL", ""), ("LANG", "en_US.UTF-8")]);
+        assert_eq!(with_env(&env, from_env), defaults_for("en_US"));
+
+        assert_eq!(with_env(&UNSET, from_env), None);
+    }
+
+    #[test]
+    fn apply_fills_only_unset_keys() {
+        let mut merged: toml::Table = toml::from_str(
+            "auto_locale = true\n[theme]\nclock_format = \"%H.%M\"\n",
+        )
+        .unwrap();
+        with_env(&locale_env(&[("LANG", "en_US.UTF-8")]), || apply(&mut merged));
+        assert_eq!(merged["theme"]["clock_format"].as_str(), Some("%H.%M"));
+        assert_eq!(merged["theme"]["temperature_unit"].as_str(), Some("f"));
+    }
+
+    #[test]
+    fn apply_does_nothing_without_auto_locale() {
+        let mut merged: toml::Table = toml::from_str("[theme]\n").unwrap();
+        with_env(&locale_env(&[("LANG", "en_US.UTF-8")]), || apply(&mut merged));
+        assert_eq!(merged["theme"].as_table().map(toml::Table::len), Some(0));
+    }
+}
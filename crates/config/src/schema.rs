@@ -1,13 +1,57 @@
 use serde::{Deserialize, Serialize};
 
 /// Root configuration structure parsed from `bar.toml`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DashConfig {
     /// Command to run for the Lock action in the power menu.
     pub lock_command: String,
     /// City name for wttr.in weather card (e.g. `"London"`).  Empty = disabled.
     pub weather_location: String,
+    /// Case-insensitive keywords: any `(...)` or `[...]` group in a media title
+    /// containing one of these is removed (e.g. `"Song (Official Video) [4K]"` → `"Song"`).
+    pub media_strip_tags: Vec<String>,
+    /// Patterns removed from media titles wherever they match, after the
+    /// tags above.  These are globs, not regular expressions: `*` matches any
+    /// run of characters, `?` any one character, and everything else —
+    /// including `.`, `^`, `$`, `|` and `\` — only itself, ignoring case
+    /// (e.g. `"(feat. *)"`, `"| * Records"`).  Entries that look like
+    /// regexes are logged when the config loads.
+    pub media_strip_patterns: Vec<String>,
+    /// MPRIS player the media card controls at startup, as listed by
+    /// `playerctl -l` (e.g. `"spotify"`).  Empty = playerctl's default.
+    pub media_player: String,
+    /// Hide the media card once the player has been paused or stopped for
+    /// this many seconds (`0` = as soon as it isn't playing).  Unset = always shown.
+    pub media_hide_paused_after: Option<u64>,
+    /// The `"peripherals"` card only lists devices at or below this battery
+    /// percentage, e.g. `30` to show just what needs charging.  Unset = all.
+    pub peripherals_only_below: Option<u8>,
+    /// Wi-Fi picker backend: `"networkmanager"` (default, uses `nmcli`) or
+    /// `"iwd"` (uses `iwctl`).
+    pub wifi_backend: String,
+    /// Seconds between root filesystem usage reads (CPU, memory, network and
+    /// audio are read every 2 seconds).
+    pub disk_interval_secs: u64,
+    /// Seconds between `checkupdates` runs for the updates card.
+    pub updates_interval_secs: u64,
+    /// Seconds between weather lookups.
+    pub weather_interval_secs: u64,
+    /// Shell commands run (via `sh -c`, not awaited) when the dashboard starts.
+    pub on_start: Vec<String>,
+    /// Shell commands run whenever the config is reloaded after a change.
+    pub on_reload: Vec<String>,
+    /// Durations offered by the `"caffeine"` card, in minutes.  Click starts
+    /// the selected one, right-click moves to the next.
+    pub caffeine_minutes: Vec<u32>,
+    /// Address to serve Prometheus metrics on at `/metrics` while the
+    /// dashboard is open, as IP and port, e.g. `"127.0.0.1:9633"`.  Empty = disabled.
+    pub metrics_listen: String,
+    /// Take the temperature unit and clock format from `$LC_TIME`/`$LANG`
+    /// (e.g. `en_US` → Fahrenheit and `%I:%M %p`) unless `[theme]` sets them.
+    pub auto_locale: bool,
+    /// Extra actions for the command palette (`[[palette]]` tables).
+    pub palette: Vec<PaletteEntry>,
     /// Theme / visual settings.
     pub theme: ThemeConfig,
     /// Bento dashboard overlay settings.
@@ -19,14 +63,47 @@ impl Default for DashConfig {
         Self {
             lock_command:     "loginctl lock-session".to_string(),
             weather_location: String::new(),
+            media_strip_tags: default_media_strip_tags(),
+            media_strip_patterns: Vec::new(),
+            media_player:     String::new(),
+            media_hide_paused_after: None,
+            peripherals_only_below: None,
+            wifi_backend:     "networkmanager".to_string(),
+            disk_interval_secs:    30,
+            updates_interval_secs: 1800,
+            weather_interval_secs: 600,
+            on_start:         Vec::new(),
+            on_reload:        Vec::new(),
+            caffeine_minutes: vec![30, 60, 120],
+            metrics_listen:   String::new(),
+            auto_locale:      false,
+            palette:          Vec::new(),
             theme:            ThemeConfig::default(),
             dashboard:        DashboardConfig::default(),
         }
     }
 }
 
+fn default_media_strip_tags() -> Vec<String> {
+    ["official", "lyric", "remaster", "visualizer", "audio", "video", "hd", "4k"]
+        .iter()
+        .map(|&tag| tag.to_string())
+        .collect()
+}
+
+/// A user-defined command palette action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaletteEntry {
+    /// Text shown in the palette and matched against the search.
+    pub label: String,
+    /// Shell command run (via `sh -c`) when the entry is chosen; the
+    /// dashboard closes afterwards.
+    pub command: String,
+}
+
 /// Per-card layout configuration inside the bento dashboard grid.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct CardConfig {
     /// Card type identifier, e.g. `"clock"`, `"cpu"`, etc.
@@ -35,11 +112,39 @@ pub struct CardConfig {
     pub col_span: u8,
     /// Number of rows this card spans (1–3).
     pub row_span: u8,
+    /// Minimum card width in pixels, so changing values don't resize the card.
+    /// `0.0` (default) uses the theme's base width.
+    pub min_width: f32,
+    /// Text template for the `"metrics"` card, e.g. `"CPU {cpu} MEM {mem}"`.
+    /// Empty = the card's built-in default.
+    pub template: String,
+    /// Cards shown side by side inside a `"group"` card, split by thin
+    /// separators.  Groups don't nest.
+    pub children: Vec<CardConfig>,
+    /// For groups: show only the first child until the card is clicked, then
+    /// slide the rest out.
+    pub drawer: bool,
+    /// Hyprland dispatcher run when the card is clicked, e.g.
+    /// `"exec kitty"` or `"workspace 2"`.  Empty = none.
+    pub dispatch: String,
+    /// `false` hides the card but keeps its place and options in the
+    /// layout, so it can be switched back on without re-adding it.
+    pub enabled: bool,
 }
 
 impl Default for CardConfig {
     fn default() -> Self {
-        Self { kind: String::new(), col_span: 1, row_span: 1 }
+        Self {
+            kind:      String::new(),
+            col_span:  1,
+            row_span:  1,
+            min_width: 0.0,
+            template:  String::new(),
+            children:  Vec::new(),
+            drawer:    false,
+            dispatch:  String::new(),
+            enabled:   true,
+        }
     }
 }
 
@@ -47,7 +152,7 @@ impl Default for CardConfig {
 ///
 /// Launch with `bar-dashboard` — bind it to a Hyprland key:
 /// `bind = SUPER, D, exec, bar-dashboard`
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DashboardConfig {
     /// When `false`, `bar-dashboard` is a no-op (exits immediately).
@@ -56,11 +161,25 @@ pub struct DashboardConfig {
     pub theme: String,
     /// Number of columns in the bento grid (2–4).  Default: 3.
     pub columns: u8,
+    /// Layer-shell namespace of the overlay surface, for Hyprland
+    /// `layerrule`s (`layerrule = blur, bar-dashboard`).  Read at startup.
+    pub namespace: String,
+    /// Layer-shell layer: `"overlay"` (default, above everything),
+    /// `"top"`, `"bottom"` or `"background"`.  Read at startup.
+    pub layer: String,
+    /// `-1` (default) covers other panels' reserved space too; `0` keeps
+    /// the overlay inside it, so bars stay visible.  Read at startup.
+    pub exclusive_zone: i32,
+    /// `"ltr"` (default) or `"rtl"`: right-to-left mirrors the grid, so each
+    /// row and group is laid out from the right.
+    pub direction: String,
     /// Ordered list of cards to display, each with optional span overrides.
     /// Possible `kind` values: `"clock"`, `"network"`, `"battery"`, `"cpu"`, `"memory"`,
     /// `"disk"`, `"volume"`, `"brightness"`, `"media"`, `"power"`,
     /// `"uptime"`, `"temperature"`, `"updates"`,
-    /// `"swap"`, `"load"`, `"gpu"`, `"bluetooth"`, `"weather"`.
+    /// `"swap"`, `"load"`, `"gpu"`, `"bluetooth"`, `"peripherals"`, `"weather"`, `"self"`, `"host"`,
+    /// `"spacer"` (empty gap, no card background), `"debug"`, `"metrics"` (see `template`),
+    /// `"group"` (see `children`).
     pub items: Vec<CardConfig>,
 }
 
@@ -70,20 +189,34 @@ impl Default for DashboardConfig {
             enabled: true,
             theme:   "cards".to_string(),
             columns: 3,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            layer:     "overlay".to_string(),
+            exclusive_zone: -1,
+            direction: "ltr".to_string(),
             items:   default_dashboard_items(),
         }
     }
 }
 
+/// Namespace used when `dashboard.namespace` is unset or empty.
+pub const DEFAULT_NAMESPACE: &str = "bar-dashboard";
+
+impl DashboardConfig {
+    /// The configured `namespace`, or [`DEFAULT_NAMESPACE`] if it's empty.
+    pub fn layer_namespace(&self) -> &str {
+        if self.namespace.trim().is_empty() { DEFAULT_NAMESPACE } else { self.namespace.trim() }
+    }
+}
+
 fn default_dashboard_items() -> Vec<CardConfig> {
     ["clock", "network", "battery", "cpu", "memory", "disk", "volume", "media", "power"]
         .iter()
-        .map(|&kind| CardConfig { kind: kind.to_string(), col_span: 1, row_span: 1 })
+        .map(|&kind| CardConfig { kind: kind.to_string(), ..Default::default() })
         .collect()
 }
 
 /// Theme / styling configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ThemeConfig {
     /// Background color (hex, e.g. `"#1e1e2e"`).
@@ -92,6 +225,10 @@ pub struct ThemeConfig {
     pub foreground: String,
     /// Accent / highlight color.
     pub accent: String,
+    /// Color for values that need attention (e.g. a weak Wi-Fi signal).
+    pub warning: String,
+    /// Color for values in a bad state (e.g. a barely usable Wi-Fi signal).
+    pub critical: String,
     /// Font family name.
     pub font: String,
     /// Font size in points.
@@ -110,6 +247,8 @@ pub struct ThemeConfig {
     pub widget_border_width: u32,
     /// `strftime`-style time format string (default: `"%H:%M"`).
     pub clock_format: String,
+    /// Temperatures in `"c"` (default, Celsius) or `"f"` (Fahrenheit).
+    pub temperature_unit: String,
     /// `strftime`-style date format string (default: `"%a %d %b"`).
     pub date_format: String,
     /// Icon style: `"nerd"` uses Nerd Font glyphs; `"ascii"` uses plain text labels.
@@ -120,11 +259,65 @@ pub struct ThemeConfig {
     pub widget_padding_y: u16,
     /// When `true`, the clock widget appends seconds to the time display.
     pub clock_show_seconds: bool,
+    /// Show the date under the time on the clock card.  When `false` the
+    /// date only appears on hover.
+    pub clock_show_date: bool,
     /// Battery percentage at which the battery widget shows a low-power glyph.
     pub battery_warn_percent: u8,
+    /// Root filesystem usage (percent) at which the disk card turns the
+    /// `warning` colour and glows.
+    pub disk_warn_percent: u8,
+    /// Usage at which the disk card turns the `critical` colour.
+    pub disk_critical_percent: u8,
     /// Visual style for power menu action buttons.
     /// `"icon_label"` (default), `"icon_only"`, `"pill"`.
     pub power_button_style: String,
+    /// Maximum characters shown for the media card's track title (ellipsis included).
+    pub media_max_chars: usize,
+    /// Maximum characters shown for the Bluetooth card's device name (ellipsis included).
+    pub bluetooth_max_chars: usize,
+    /// Host card contents: `"user_host"` (default, `user@hostname`) or `"host"`.
+    pub host_show: String,
+    /// Volume slider step in percent.
+    pub volume_step: u8,
+    /// Brightness slider step in percent.
+    pub brightness_step: u8,
+    /// Speed up scrolling on the volume and brightness cards: wheel notches
+    /// less than 100ms apart move by up to four steps each.
+    pub scroll_acceleration: bool,
+    /// Highest volume the dashboard will set, as a fraction (`1.0` = 100%, max `1.5`).
+    pub volume_max: f32,
+    /// Wi-Fi signal after the interface name on the network card: `"off"`
+    /// (default), `"pct"` (`wlan0 · 78%`) or `"bars"` (`wlan0 ▂▄▆`).
+    pub network_signal: String,
+    /// Show the active audio output (speakers, headphones, HDMI, Bluetooth)
+    /// as the volume card's icon and label.  Needs `pactl`.
+    pub volume_show_port: bool,
+    /// Right-align percentages to a fixed width (`" 9%"`, `"10%"`) so values
+    /// don't shift as they gain or lose a digit.  The padding is figure
+    /// spaces (U+2007), which are digit-width in any font.
+    pub pad_numbers: bool,
+    /// Byte sizes in `"iec"` (default, powers of 1024: `1.5G`) or `"si"`
+    /// (powers of 1000: `1.6GB`) units.
+    pub byte_units: String,
+    /// Decimal separator for numbers on the cards and in `{placeholders}`:
+    /// `"."` (default) or `","` (`1,5G`, load `0,42`).
+    pub decimal_separator: String,
+    /// Network speeds as `"bytes"` (default, follows `byte_units`) or `"bits"`
+    /// (`12Mb`, as ISPs quote them).
+    pub rate_units: String,
+    /// Draw a drop shadow under each card.
+    pub shadow: bool,
+    /// Shadow colour as `#RRGGBB` or `#RRGGBBAA`.
+    pub shadow_color: String,
+    /// Shadow blur radius in pixels.
+    pub shadow_blur: f32,
+    /// Downward shadow offset in pixels.
+    pub shadow_offset: f32,
+    /// Turn off animations (intro fade, drawer slides, equalizer bars):
+    /// `true`, `false`, or `"auto"` to follow the desktop's reduced-motion
+    /// setting (`org.freedesktop.appearance` via the settings portal).
+    pub reduce_motion: ReduceMotion,
 }
 
 impl Default for ThemeConfig {
@@ -133,6 +326,8 @@ impl Default for ThemeConfig {
             background:          "#1e1e2e".to_string(), // Catppuccin Mocha — base
             foreground:          "#cdd6f4".to_string(), // Catppuccin Mocha — text
             accent:              "#cba6f7".to_string(), // Catppuccin Mocha — mauve
+            warning:             "#f9e2af".to_string(), // Catppuccin Mocha — yellow
+            critical:            "#f38ba8".to_string(), // Catppuccin Mocha — red
             font:                "JetBrains Mono".to_string(),
             font_size:           13.0,
             border_radius:       6.0,
@@ -142,13 +337,44 @@ impl Default for ThemeConfig {
             widget_border_color: String::new(),
             widget_border_width: 0,
             clock_format:        "%H:%M".to_string(),
+            temperature_unit:    "c".to_string(),
             date_format:         "%a %d %b".to_string(),
             icon_style:          "nerd".to_string(),
             widget_padding_x:    8,
             widget_padding_y:    4,
             clock_show_seconds:  false,
+            clock_show_date:     true,
             battery_warn_percent: 20,
+            disk_warn_percent:   85,
+            disk_critical_percent: 95,
             power_button_style:  "icon_label".to_string(),
+            media_max_chars:     20,
+            bluetooth_max_chars: 14,
+            host_show:           "user_host".to_string(),
+            volume_step:         5,
+            brightness_step:     5,
+            scroll_acceleration: false,
+            volume_max:          1.0,
+            network_signal:      "off".to_string(),
+            volume_show_port:    false,
+            pad_numbers:         false,
+            byte_units:          "iec".to_string(),
+            decimal_separator:   ".".to_string(),
+            rate_units:          "bytes".to_string(),
+            shadow:              false,
+            shadow_color:        "#00000080".to_string(),
+            shadow_blur:         16.0,
+            shadow_offset:       4.0,
+            reduce_motion:       ReduceMotion::Set(false),
         }
     }
 }
+
+/// Value of `theme.reduce_motion`: a plain boolean, or a mode string.
+/// `"auto"` is the only mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReduceMotion {
+    Set(bool),
+    Mode(String),
+}
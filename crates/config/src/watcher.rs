@@ -2,38 +2,55 @@ use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 
-/// Watches a config file for changes and sends a notification on every write.
+/// Watches one or more config files and reports which one changed on every write.
 ///
 /// # Example
 /// ```no_run
-/// let (_, mut rx) = ConfigWatcher::spawn("/home/user/.config/bar/bar.toml");
-/// while rx.recv().await.is_some() {
-///     println!("config changed — reloading");
+/// # use bar_config::ConfigWatcher;
+/// # async fn run() {
+/// let (_, mut rx) = ConfigWatcher::spawn(["/home/user/.config/bar/bar.toml"]);
+/// while let Some(path) = rx.recv().await {
+///     println!("{} changed — reloading", path.display());
 /// }
+/// # }
 /// ```
 pub struct ConfigWatcher {
-    path: PathBuf,
+    paths: Vec<PathBuf>,
 }
 
 impl ConfigWatcher {
-    /// Spawn a filesystem watcher for `path`.
-    /// Returns the watcher handle and a receiver that fires on every detected change.
-    pub fn spawn(path: impl AsRef<Path>) -> (Self, mpsc::Receiver<()>) {
-        let (tx, rx) = mpsc::channel(1);
-        let path = path.as_ref().to_path_buf();
-        let watcher = Self { path: path.clone() };
+    /// Spawn a filesystem watcher for every path in `paths`.
+    /// Returns the watcher handle and a receiver that yields the watched path
+    /// behind each detected change.
+    pub fn spawn<I, P>(paths: I) -> (Self, mpsc::Receiver<PathBuf>)
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let (tx, rx) = mpsc::channel(8);
+        let paths: Vec<PathBuf> = paths.into_iter().map(|p| p.as_ref().to_path_buf()).collect();
+        let watcher = Self { paths: paths.clone() };
 
-        tokio::spawn(watch_loop(path, tx));
+        tokio::spawn(watch_loop(paths, tx));
 
         (watcher, rx)
     }
 
-    pub fn path(&self) -> &Path {
-        &self.path
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
     }
 }
 
-async fn watch_loop(path: PathBuf, tx: mpsc::Sender<()>) {
+/// Map a raw event path back to the watched path it belongs to — either the
+/// path itself, or the watched directory containing it.
+fn owning_path<'a>(watched: &'a [PathBuf], changed: &Path) -> Option<&'a PathBuf> {
+    watched
+        .iter()
+        .find(|w| changed == w.as_path())
+        .or_else(|| watched.iter().find(|w| changed.parent() == Some(w.as_path())))
+}
+
+async fn watch_loop(paths: Vec<PathBuf>, tx: mpsc::Sender<PathBuf>) {
     use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
     use std::time::Duration;
     use tokio::sync::mpsc as async_mpsc;
@@ -53,20 +70,37 @@ async fn watch_loop(path: PathBuf, tx: mpsc::Sender<()>) {
         }
     };
 
-    if let Err(e) = watcher.watch(&path, RecursiveMode::NonRecursive) {
-        error!("Failed to watch '{}': {e}", path.display());
-        return;
+    for path in &paths {
+        // A path that doesn't exist yet (no `conf.d/`) is watched through its
+        // parent directory, so its creation is still reported.
+        let target = match path.parent() {
+            Some(parent) if !path.exists() => parent,
+            _ => path.as_path(),
+        };
+        match watcher.watch(target, RecursiveMode::NonRecursive) {
+            Ok(()) => info!("Watching config file: {}", path.display()),
+            Err(e) => error!("Failed to watch '{}': {e}", path.display()),
+        }
     }
 
-    info!("Watching config file: {}", path.display());
-
     while let Some(event) = sync_rx.recv().await {
         match event {
             Ok(e) => {
                 use notify::EventKind::*;
-                if matches!(e.kind, Modify(_) | Create(_)) {
-                    if tx.send(()).await.is_err() {
-                        break; // receiver dropped
+                if !matches!(e.kind, Modify(_) | Create(_) | Remove(_)) {
+                    continue;
+                }
+                let removed = matches!(e.kind, Remove(_));
+                for changed in &e.paths {
+                    let Some(owner) = owning_path(&paths, changed) else { continue };
+                    // Removal only matters inside a watched directory (a drop-in
+                    // going away); on a watched file it's usually an editor's
+                    // atomic save, followed by a Create.
+                    if removed && !owner.is_dir() {
+                        continue;
+                    }
+                    if tx.send(owner.clone()).await.is_err() {
+                        return; // receiver dropped
                     }
                 }
             }
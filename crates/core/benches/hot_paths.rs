@@ -0,0 +1,127 @@
+//! Timings for the parsers and formatting helpers the dashboard runs on
+//! every snapshot and redraw.  No external harness: run with
+//! `cargo bench -p bar-core`, optionally followed by a filter
+//! (`cargo bench -p bar-core -- upower`).
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bar_core::{
+    devices::{parse_upower_dump, parse_wireless},
+    format_bits, format_bytes,
+    layout::grid_layout,
+    localize_decimal,
+    media::{decode_entities, strip_title_tags},
+    truncate_chars, truncate_smart, ByteUnits,
+};
+
+/// `/proc/net/wireless` with the interface on the last line.
+const WIRELESS: &str = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+ wlan1: 0000    0.  -256  -256        0      0      0      0      0        0
+wlp2s0: 0000   54.  -56.  -256        0      0      0      0     12        0
+";
+
+/// `upower --dump` of a laptop with a mouse, a keyboard and a headset.
+const UPOWER_DUMP: &str = "\
+Device: /org/freedesktop/UPower/devices/line_power_AC
+  native-path:          AC
+  power supply:         yes
+  line-power
+    online:              yes
+
+Device: /org/freedesktop/UPower/devices/battery_BAT0
+  native-path:          BAT0
+  model:                5B10W13975
+  battery
+    state:               discharging
+    percentage:          81%
+
+Device: /org/freedesktop/UPower/devices/mouse_hidpp_battery_0
+  native-path:          hidpp_battery_0
+  model:                MX Master 3
+  mouse
+    percentage:          55%
+
+Device: /org/freedesktop/UPower/devices/keyboard_dev_DC_2C_26_01_02_03
+  model:                K380
+  keyboard
+    percentage:          20%
+
+Device: /org/freedesktop/UPower/devices/headset_dev_00_1B_66_AA_BB_CC
+  model:                WH-1000XM4
+  headset
+    percentage:          50% (should be ignored)
+";
+
+/// How long each case is run for after warming up.
+const MEASURE: Duration = Duration::from_millis(500);
+
+/// Run `f` repeatedly for about [`MEASURE`] and print the mean time per call.
+fn bench<T>(name: &str, filter: Option<&str>, mut f: impl FnMut() -> T) {
+    if filter.is_some_and(|pat| !name.contains(pat)) {
+        return;
+    }
+    for _ in 0..1_000 {
+        black_box(f());
+    }
+    let mut iters: u64 = 0;
+    let start = Instant::now();
+    while start.elapsed() < MEASURE {
+        for _ in 0..100 {
+            black_box(f());
+        }
+        iters += 100;
+    }
+    let per_call = start.elapsed().as_nanos() as f64 / iters as f64;
+    println!("{name:<32} {per_call:>10.1} ns/iter  ({iters} iterations)");
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`; anything else is a name filter.
+    let filter = std::env::args().skip(1).find(|a| !a.starts_with("--"));
+    let filter = filter.as_deref();
+
+    let sizes = [0u64, 1_536, 1_572_864, 512 * 1_048_576, 3 * 1_073_741_824 / 2];
+    bench("format_bytes/iec", filter, || {
+        sizes.map(|b| format_bytes(black_box(b), ByteUnits::Iec))
+    });
+    bench("format_bytes/si", filter, || {
+        sizes.map(|b| format_bytes(black_box(b), ByteUnits::Si))
+    });
+    bench("format_bits", filter, || sizes.map(|b| format_bits(black_box(b))));
+    bench("localize_decimal/comma", filter, || {
+        localize_decimal(black_box("12.5G / 15.6G".to_string()), ',')
+    });
+    bench("localize_decimal/point", filter, || {
+        localize_decimal(black_box("12.5G / 15.6G".to_string()), '.')
+    });
+
+    let title = "The Quick Brown Fox Jumps Over the Lazy Dog (Remastered 2011)";
+    let cjk   = "東京事変の新しいアルバムからのシングル曲";
+    bench("truncate_smart/ascii", filter, || truncate_smart(black_box(title), 28, "\u{2026}"));
+    bench("truncate_smart/cjk", filter, || truncate_smart(black_box(cjk), 12, "\u{2026}"));
+    bench("truncate_smart/short", filter, || truncate_smart(black_box("Song"), 28, "\u{2026}"));
+    bench("truncate_chars", filter, || truncate_chars(black_box(title), 28).len());
+
+    bench("parse_wireless", filter, || parse_wireless(black_box(WIRELESS), "wlp2s0"));
+    bench("parse_upower_dump", filter, || parse_upower_dump(black_box(UPOWER_DUMP)));
+
+    let tags: Vec<String> = ["official", "lyric", "remaster", "4k"].map(String::from).to_vec();
+    let patterns: Vec<String> = ["(feat. *)", "| * records"].map(String::from).to_vec();
+    let tagged = "Song Title (Official Video) [4K] (feat. Somebody) | Big Records";
+    bench("strip_title_tags/tags", filter, || strip_title_tags(black_box(tagged), &tags, &[]));
+    bench("strip_title_tags/patterns", filter, || {
+        strip_title_tags(black_box(tagged), &tags, &patterns)
+    });
+    bench("decode_entities/plain", filter, || decode_entities(black_box(title)));
+    bench("decode_entities/escaped", filter, || {
+        decode_entities(black_box("Simon &amp; Garfunkel &#8211; Caf&#233; &quot;Live&quot;"))
+    });
+
+    // Fourteen cards, the clock and media among them two columns wide.
+    let spans = [2, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 1];
+    bench("grid_layout/ltr", filter, || grid_layout(black_box(&spans), 4, false));
+    bench("grid_layout/rtl", filter, || grid_layout(black_box(&spans), 4, true));
+}
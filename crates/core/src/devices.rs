@@ -0,0 +1,217 @@
+//! Parsers for what the kernel and UPower report about wireless links and
+//! battery-powered peripherals.  They take the text already read, so they
+//! run (and are tested and benchmarked) without the devices.
+
+// ── Wi-Fi ─────────────────────────────────────────────────────────────────────
+
+/// One interface's line of `/proc/net/wireless`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WifiSignal {
+    /// Link quality, usually out of 70.
+    pub link:  Option<f32>,
+    /// Signal level in dBm.
+    pub level: Option<f32>,
+}
+
+impl WifiSignal {
+    /// The level, if the driver reports a plausible dBm value.
+    pub fn dbm(self) -> Option<i32> {
+        self.level.filter(|l| (-110.0..0.0).contains(l)).map(|l| l.round() as i32)
+    }
+
+    /// Approximate quality in percent: -100 dBm and below is 0%, -50 dBm and
+    /// above 100%.  Drivers without a level fall back to link quality.
+    pub fn pct(self) -> Option<u8> {
+        let pct = match (self.dbm(), self.link) {
+            (Some(dbm), _)                   => 2.0 * (dbm as f32 + 100.0),
+            (None, Some(link)) if link > 0.0 => link / 70.0 * 100.0,
+            _                                => return None,
+        };
+        Some(pct.clamp(0.0, 100.0).round() as u8)
+    }
+}
+
+/// Find `iface` in the contents of `/proc/net/wireless`:
+///
+/// ```text
+/// Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+///  face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+///  wlan0: 0000   70.  -38.  -256        0      0      0      0      0        0
+/// ```
+///
+/// Values may carry a trailing `.` (set when the driver updated them).
+#[must_use]
+pub fn parse_wireless(raw: &str, iface: &str) -> Option<WifiSignal> {
+    if iface.is_empty() {
+        return None;
+    }
+    raw.lines().skip(2).find_map(|line| {
+        let (name, rest) = line.split_once(':')?;
+        if name.trim() != iface {
+            return None;
+        }
+        let num = |field: Option<&str>| field?.trim_end_matches('.').parse::<f32>().ok();
+        let mut fields = rest.split_whitespace().skip(1); // status
+        let link  = num(fields.next());
+        let level = num(fields.next());
+        Some(WifiSignal { link, level })
+    })
+}
+
+// ── Peripherals ───────────────────────────────────────────────────────────────
+
+/// A wireless peripheral's battery as reported by UPower.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Peripheral {
+    /// UPower device type: `"mouse"`, `"keyboard"`, `"headset"`, …
+    pub kind: String,
+    pub name: String,
+    pub pct:  u8,
+}
+
+/// UPower device types the peripherals card lists.
+pub const PERIPHERAL_KINDS: [&str; 5] = ["mouse", "keyboard", "headset", "headphones", "gaming-input"];
+
+/// Whether a `upower --monitor-detail` line announces a peripheral being
+/// added, removed or changed, e.g.
+/// `[10:22:13.123] device changed:     /org/freedesktop/UPower/devices/mouse_hidpp_battery_0`.
+/// The laptop battery and AC adapter change often and are left out.
+#[must_use]
+pub fn peripheral_event(line: &str) -> bool {
+    let Some(path) = ["device added:", "device removed:", "device changed:"]
+        .iter()
+        .find_map(|event| line.split_once(event).map(|(_, path)| path.trim()))
+    else {
+        return false;
+    };
+    let device = path.rsplit('/').next().unwrap_or(path);
+    PERIPHERAL_KINDS.iter().any(|kind| device.starts_with(&kind.replace('-', "_")))
+}
+
+/// Parse `upower --dump`: one `Device:` block per device, with the device
+/// type as a bare indented word (`  mouse`) followed by its properties.
+/// Levels UPower marks `(should be ignored)` are coarse guesses and skipped.
+#[must_use]
+pub fn parse_upower_dump(raw: &str) -> Vec<Peripheral> {
+    let mut found: Vec<Peripheral> = raw
+        .split("Device: ")
+        .skip(1)
+        .filter_map(|block| {
+            let kind = block.lines().skip(1).map(str::trim).find(|l| !l.is_empty() && !l.contains(':'))?;
+            if !PERIPHERAL_KINDS.contains(&kind) {
+                return None;
+            }
+            let field = |key: &str| block.lines().find_map(|l| l.trim().strip_prefix(key).map(str::trim));
+            let level = field("percentage:")?;
+            if level.contains("ignored") {
+                return None;
+            }
+            let pct = level.split('%').next()?.trim().parse::<f32>().ok()?;
+            Some(Peripheral {
+                kind: kind.to_string(),
+                name: field("model:").filter(|m| !m.is_empty()).unwrap_or(kind).to_string(),
+                pct:  pct.round().clamp(0.0, 100.0) as u8,
+            })
+        })
+        .collect();
+    found.sort_by_key(|p| p.pct);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIRELESS: &str = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+wlp2s0: 0000   54.  -56.  -256        0      0      0      0     12        0
+ wlan1: 0000   70   -38   -256        0      0      0      0      0        0
+";
+
+    #[test]
+    fn parses_wireless_with_and_without_trailing_dots() {
+        let dotted = parse_wireless(WIRELESS, "wlp2s0").unwrap();
+        assert_eq!((dotted.link, dotted.level), (Some(54.0), Some(-56.0)));
+        assert_eq!(dotted.dbm(), Some(-56));
+        assert_eq!(dotted.pct(), Some(88));
+
+        let plain = parse_wireless(WIRELESS, "wlan1").unwrap();
+        assert_eq!((plain.link, plain.level), (Some(70.0), Some(-38.0)));
+        assert_eq!(plain.pct(), Some(100));
+    }
+
+    #[test]
+    fn wireless_header_and_unknown_interfaces_give_nothing() {
+        assert!(parse_wireless(WIRELESS, "").is_none());
+        assert!(parse_wireless(WIRELESS, "eth0").is_none());
+        // Header lines are skipped even though they contain `face`/`Inter-`.
+        assert!(parse_wireless(WIRELESS, "face").is_none());
+        assert!(parse_wireless(WIRELESS, "Inter-").is_none());
+        // An empty table: no wireless interface is up.
+        assert!(parse_wireless(&WIRELESS.lines().take(2).collect::<Vec<_>>().join("\n"), "wlp2s0").is_none());
+    }
+
+    #[test]
+    fn wireless_level_without_dbm_falls_back_to_link_quality() {
+        // Some drivers report the level as 0 or as a positive quality value.
+        let raw = "h1\nh2\nwlan0: 0000   35.    0.     0.        0      0      0      0      0        0\n";
+        let signal = parse_wireless(raw, "wlan0").unwrap();
+        assert_eq!(signal.dbm(), None);
+        assert_eq!(signal.pct(), Some(50));
+
+        let raw = "h1\nh2\nwlan0: 0000   0.   0.   0.\n";
+        assert_eq!(parse_wireless(raw, "wlan0").unwrap().pct(), None);
+    }
+
+    const UPOWER_DUMP: &str = "\
+Device: /org/freedesktop/UPower/devices/battery_BAT0
+  native-path:          BAT0
+  power supply:         yes
+  battery
+    percentage:          81%
+
+Device: /org/freedesktop/UPower/devices/mouse_hidpp_battery_0
+  native-path:          hidpp_battery_0
+  model:                MX Master 3
+  mouse
+    percentage:          55%
+
+Device: /org/freedesktop/UPower/devices/keyboard_dev_DC_2C_26_01_02_03
+  model:
+  keyboard
+    percentage:          9.6%
+
+Device: /org/freedesktop/UPower/devices/headset_dev_00_1B_66_AA_BB_CC
+  model:                WH-1000XM4
+  headset
+    percentage:          50% (should be ignored)
+";
+
+    #[test]
+    fn upower_dump_lists_peripherals_lowest_first() {
+        let found = parse_upower_dump(UPOWER_DUMP);
+        let summary: Vec<(&str, &str, u8)> =
+            found.iter().map(|p| (p.kind.as_str(), p.name.as_str(), p.pct)).collect();
+        // The laptop battery isn't a peripheral; an ignored level is a guess.
+        assert_eq!(summary, vec![("keyboard", "keyboard", 10), ("mouse", "MX Master 3", 55)]);
+        assert!(parse_upower_dump("").is_empty());
+    }
+
+    #[test]
+    fn peripheral_events_from_upower_monitor() {
+        let line = |event: &str, device: &str| {
+            format!("[10:22:13.123]\tdevice {event}:     /org/freedesktop/UPower/devices/{device}")
+        };
+        assert!(peripheral_event(&line("changed", "mouse_hidpp_battery_0")));
+        assert!(peripheral_event(&line("added", "headset_dev_00_1B_66_AA_BB_CC")));
+        assert!(peripheral_event(&line("removed", "keyboard_dev_DC_2C_26_01_02_03")));
+        assert!(peripheral_event(&line("changed", "gaming_input_dev_40_8E_2C_11_22_33")));
+
+        assert!(!peripheral_event(&line("changed", "battery_BAT0")));
+        assert!(!peripheral_event(&line("changed", "line_power_AC")));
+        assert!(!peripheral_event("[10:22:13.123]\tdaemon changed:"));
+        assert!(!peripheral_event("  percentage:          40%"));
+        assert!(!peripheral_event("Monitoring activity from the power daemon. Press Ctrl+C to cancel."));
+    }
+}
@@ -1,16 +1,58 @@
+use std::path::{Path, PathBuf};
+
 use thiserror::Error;
 
 /// Top-level error type used across the entire application.
 #[derive(Debug, Error)]
 pub enum BarError {
-    #[error("config error: {0}")]
-    Config(String),
+    /// A config file (main, include or drop-in) couldn't be read.
+    #[error("cannot read config '{}': {source}", .path.display())]
+    ConfigRead {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A config file was read but isn't valid TOML/JSON, or doesn't match
+    /// the schema.  `location` is the 1-based line and column, when known.
+    #[error("{}", parse_message(.path, .location, .message))]
+    ConfigParse {
+        path:     PathBuf,
+        location: Option<(usize, usize)>,
+        message:  String,
+    },
+
+    /// Hyprland isn't running, or its instance can't be identified.
+    #[error("Hyprland IPC unavailable: {0}")]
+    IpcUnavailable(&'static str),
+
+    /// No socket directory for the running instance was found.
+    #[error("no Hyprland socket found; tried {}", display_paths(.tried))]
+    IpcNoSocket { tried: Vec<PathBuf> },
+
+    /// The compositor socket exists in name but can't be connected to.
+    #[error("cannot connect to Hyprland at '{}': {source}", .path.display())]
+    IpcConnect {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 
-    #[error("IPC error: {0}")]
-    Ipc(String),
+    /// Hyprland answered, but not with what was asked for.
+    #[error("Hyprland rejected `{request}`: {reply}")]
+    IpcProtocol { request: String, reply: String },
 
-    #[error("system error: {0}")]
-    System(String),
+    /// No reply within the socket's read timeout.
+    #[error("Hyprland did not answer `{request}` in time")]
+    IpcTimeout { request: String },
+
+    /// A D-Bus call failed or returned something unusable.
+    #[error("D-Bus call {call} failed: {reason}")]
+    Dbus { call: String, reason: String },
+
+    /// Reading a system source (`/proc`, `/sys`, a helper tool) failed.
+    #[error("could not read {what}: {reason}")]
+    SystemProbe { what: String, reason: String },
 
     #[error("wayland error: {0}")]
     Wayland(String),
@@ -25,4 +67,40 @@ pub enum BarError {
     },
 }
 
+impl BarError {
+    /// A short message worth showing the user, for errors they can act on
+    /// (fix the config, start Hyprland).  `None` means log-only.
+    pub fn user_facing(&self) -> Option<String> {
+        match self {
+            Self::ConfigRead { .. } | Self::ConfigParse { .. } => {
+                Some(format!("{self} — the previous settings stay in effect until it's fixed."))
+            }
+            Self::IpcUnavailable(_) | Self::IpcNoSocket { .. } | Self::IpcConnect { .. } => {
+                Some("Can't reach Hyprland; workspace and window features are off.".to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether trying the same thing again later may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::IpcNoSocket { .. } | Self::IpcConnect { .. } | Self::IpcTimeout { .. } | Self::Dbus { .. }
+        )
+    }
+}
+
+fn parse_message(path: &Path, location: &Option<(usize, usize)>, message: &str) -> String {
+    match *location {
+        Some((line, col)) => format!("invalid config '{}' at line {line}, column {col}: {message}", path.display()),
+        None              => format!("invalid config '{}': {message}", path.display()),
+    }
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    let shown: Vec<String> = paths.iter().map(|p| format!("'{}'", p.display())).collect();
+    shown.join(", ")
+}
+
 pub type Result<T, E = BarError> = std::result::Result<T, E>;
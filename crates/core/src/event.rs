@@ -21,7 +21,8 @@ pub enum Message {
 
     // ── System monitor ────────────────────────────────────────────────────────
     /// Fresh system resource snapshot from the background monitor task.
-    SystemSnapshot(SystemSnapshot),
+    /// Boxed: the snapshot is several times larger than any other variant.
+    SystemSnapshot(Box<SystemSnapshot>),
 
     // ── Config ────────────────────────────────────────────────────────────────
     /// Config file changed on disk — triggers a live reload.
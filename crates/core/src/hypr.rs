@@ -0,0 +1,176 @@
+use std::io::{ErrorKind, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::{BarError, Result};
+
+/// Directory holding Hyprland's `.socket.sock` (commands) and
+/// `.socket2.sock` (events).
+///
+/// `$BAR_HYPR_SOCKET_DIR` wins when set, so the dashboard can be pointed at a
+/// nested or test compositor's sockets.  Otherwise the instance named by
+/// `$HYPRLAND_INSTANCE_SIGNATURE` is looked up under `<runtime dir>/hypr/`,
+/// then in the pre-0.40 `/tmp/hypr/` location.  Either way the directory
+/// must actually hold `.socket.sock`.  The runtime dir is
+/// `$XDG_RUNTIME_DIR`, or `/run/user/<uid>` when that isn't set.
+pub fn socket_dir() -> Result<PathBuf> {
+    let override_dir = std::env::var_os("BAR_HYPR_SOCKET_DIR")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from);
+    let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok();
+    let candidates = socket_candidates(override_dir, signature.as_deref(), runtime_dir())?;
+    first_with_socket(candidates)
+}
+
+/// Directories that may hold the sockets, most preferred first: just the
+/// override when there is one, else the instance's runtime and `/tmp` dirs.
+fn socket_candidates(
+    override_dir: Option<PathBuf>,
+    signature:    Option<&str>,
+    runtime:      Option<PathBuf>,
+) -> Result<Vec<PathBuf>> {
+    if let Some(dir) = override_dir {
+        return Ok(vec![dir]);
+    }
+    let sig = signature.ok_or(BarError::IpcUnavailable("HYPRLAND_INSTANCE_SIGNATURE is not set"))?;
+    Ok(runtime
+        .map(|d| d.join("hypr").join(sig))
+        .into_iter()
+        .chain(std::iter::once(PathBuf::from("/tmp/hypr").join(sig)))
+        .collect())
+}
+
+/// The first of `candidates` that holds `.socket.sock`.
+fn first_with_socket(candidates: Vec<PathBuf>) -> Result<PathBuf> {
+    match candidates.iter().find(|d| d.join(".socket.sock").exists()) {
+        Some(dir) => Ok(dir.clone()),
+        None      => Err(BarError::IpcNoSocket { tried: candidates }),
+    }
+}
+
+/// `$XDG_RUNTIME_DIR`, or `/run/user/<uid>` for this process's user when
+/// the variable is missing (e.g. started from a bare TTY or a service).
+fn runtime_dir() -> Option<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    // /proc/self is owned by the process's user, which saves a libc call.
+    let uid = std::fs::metadata("/proc/self").ok()?.uid();
+    Some(PathBuf::from(format!("/run/user/{uid}")))
+}
+
+/// Path of the request/response command socket (`hyprctl`'s socket).
+pub fn command_socket() -> Result<PathBuf> {
+    socket_dir().map(|d| d.join(".socket.sock"))
+}
+
+/// Path of the event stream socket.
+pub fn event_socket() -> Result<PathBuf> {
+    socket_dir().map(|d| d.join(".socket2.sock"))
+}
+
+/// Send one request over the command socket and return Hyprland's reply,
+/// e.g. `request("monitors")`.  Blocks for at most a couple of seconds.
+pub fn request(cmd: &str) -> Result<String> {
+    let path = command_socket()?;
+    let mut stream = UnixStream::connect(&path)
+        .map_err(|source| BarError::IpcConnect { path, source })?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    stream.write_all(cmd.as_bytes())?;
+
+    // Hyprland closes the connection once the reply is written.
+    let mut reply = String::new();
+    match stream.read_to_string(&mut reply) {
+        Ok(_) => Ok(reply),
+        Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+            Err(BarError::IpcTimeout { request: cmd.to_string() })
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Pid of the client owning the layer surface with this `namespace`, or
+/// `None` if no such surface is mapped.  Reads the `layers` reply, whose
+/// lines look like `Layer 55d0…: xywh: 0 0 1920 1080, namespace: bar-dashboard, pid: 4242`.
+pub fn layer_pid(namespace: &str) -> Result<Option<u32>> {
+    let reply = request("layers")?;
+    Ok(reply.lines().find_map(|line| {
+        let (_, rest) = line.split_once("namespace: ")?;
+        let (ns, pid) = rest.split_once(", pid: ")?;
+        let digits: String = pid.chars().take_while(char::is_ascii_digit).collect();
+        if ns == namespace { digits.parse().ok() } else { None }
+    }))
+}
+
+/// Run a dispatcher, e.g. `dispatch("workspace 2")`, without spawning
+/// `hyprctl`.  Returns Hyprland's reply, which is `ok` on success.
+pub fn dispatch(args: &str) -> Result<String> {
+    let reply = request(&format!("dispatch {args}"))?;
+    if reply.trim() == "ok" {
+        Ok(reply)
+    } else {
+        Err(BarError::IpcProtocol { request: format!("dispatch {args}"), reply: reply.trim().to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn with_socket(dir: &Path) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(".socket.sock"), "").unwrap();
+        dir.to_path_buf()
+    }
+
+    #[test]
+    fn candidates_prefer_the_runtime_dir_then_tmp() {
+        let dirs = socket_candidates(None, Some("abc_123"), Some(PathBuf::from("/run/user/1000"))).unwrap();
+        assert_eq!(dirs, [PathBuf::from("/run/user/1000/hypr/abc_123"), PathBuf::from("/tmp/hypr/abc_123")]);
+
+        let dirs = socket_candidates(None, Some("abc_123"), None).unwrap();
+        assert_eq!(dirs, [PathBuf::from("/tmp/hypr/abc_123")]);
+    }
+
+    #[test]
+    fn override_is_the_only_candidate() {
+        let dirs = socket_candidates(Some(PathBuf::from("/nested")), None, Some(PathBuf::from("/run/user/1000")));
+        assert_eq!(dirs.unwrap(), [PathBuf::from("/nested")]);
+    }
+
+    #[test]
+    fn missing_signature_means_no_hyprland() {
+        let err = socket_candidates(None, None, Some(PathBuf::from("/run/user/1000"))).unwrap_err();
+        assert!(matches!(err, BarError::IpcUnavailable(_)));
+    }
+
+    #[test]
+    fn first_directory_with_a_socket_wins() {
+        let tmp      = tempfile::tempdir().unwrap();
+        let empty    = tmp.path().join("runtime/hypr/sig");
+        let fallback = with_socket(&tmp.path().join("tmp/hypr/sig"));
+        std::fs::create_dir_all(&empty).unwrap();
+
+        assert_eq!(first_with_socket(vec![empty.clone(), fallback.clone()]).unwrap(), fallback);
+
+        let preferred = with_socket(&empty);
+        assert_eq!(first_with_socket(vec![preferred.clone(), fallback]).unwrap(), preferred);
+    }
+
+    #[test]
+    fn override_without_a_socket_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dirs = socket_candidates(Some(tmp.path().to_path_buf()), Some("sig"), None).unwrap();
+        match first_with_socket(dirs) {
+            Err(BarError::IpcNoSocket { tried }) => assert_eq!(tried, [tmp.path().to_path_buf()]),
+            other => panic!("expected IpcNoSocket, got {other:?}"),
+        }
+        with_socket(tmp.path());
+        let dirs = socket_candidates(Some(tmp.path().to_path_buf()), Some("sig"), None).unwrap();
+        assert_eq!(first_with_socket(dirs).unwrap(), tmp.path());
+    }
+}
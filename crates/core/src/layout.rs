@@ -0,0 +1,63 @@
+//! Placement of dashboard cards on the grid.
+
+/// Pack cards with the given spans into rows of `cols` columns, in order: a
+/// card that doesn't fit in what's left of a row starts the next one.
+/// Returns each row's card indices in display order, mirrored for
+/// right-to-left layouts.  Only the order of the cards changes; their
+/// contents (icons, ↓/↑ arrows) stay as they are.
+#[must_use]
+pub fn grid_layout(spans: &[usize], cols: usize, rtl: bool) -> Vec<Vec<usize>> {
+    let mut rows = Vec::new();
+    let mut row  = Vec::new();
+    let mut used = 0;
+    for (idx, &span) in spans.iter().enumerate() {
+        if used + span > cols && !row.is_empty() {
+            rows.push(std::mem::take(&mut row));
+            used = 0;
+        }
+        row.push(idx);
+        used += span;
+        if used >= cols {
+            rows.push(std::mem::take(&mut row));
+            used = 0;
+        }
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    if rtl {
+        rows.iter_mut().for_each(|row| row.reverse());
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_layout_packs_rows_in_order() {
+        // clock(2) cpu | memory media(2) | disk battery volume
+        let spans = [2, 1, 1, 2, 1, 1, 1];
+        assert_eq!(grid_layout(&spans, 3, false), vec![vec![0, 1], vec![2, 3], vec![4, 5, 6]]);
+        assert_eq!(grid_layout(&spans, 4, false), vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+        // media(2) doesn't fit after clock(2) and cpu: it starts a new row.
+        assert_eq!(grid_layout(&[2, 1, 2, 1], 4, false), vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(grid_layout(&[], 3, false), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn grid_layout_mirrors_each_row_for_rtl() {
+        let spans = [2, 1, 1, 1, 1];
+        assert_eq!(grid_layout(&spans, 3, false), vec![vec![0, 1], vec![2, 3, 4]]);
+        assert_eq!(grid_layout(&spans, 3, true), vec![vec![1, 0], vec![4, 3, 2]]);
+    }
+
+    #[test]
+    fn grid_layout_gives_wide_cards_their_own_row() {
+        // A span wider than what's left moves to a fresh row; one that fills
+        // the grid closes its row immediately.
+        assert_eq!(grid_layout(&[1, 3, 1], 3, false), vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(grid_layout(&[2, 2, 2], 3, true), vec![vec![0], vec![1], vec![2]]);
+    }
+}
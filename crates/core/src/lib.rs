@@ -1,8 +1,16 @@
+pub mod devices;
 pub mod error;
 pub mod event;
+pub mod hypr;
+pub mod layout;
+pub mod media;
 pub mod state;
+pub mod text;
+pub mod units;
 pub mod widget;
 
 pub use error::{BarError, Result};
 pub use event::Message;
 pub use state::AppState;
+pub use text::{truncate_chars, truncate_smart};
+pub use units::{format_bits, format_bytes, localize_decimal, ByteUnits};
@@ -0,0 +1,215 @@
+//! Cleanup of the track metadata MPRIS players report: HTML entities,
+//! "(Official Video)"-style tags and joined artist lists.
+
+/// Decode the handful of HTML entities some MPRIS players leak into metadata.
+#[must_use]
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let tail = &rest[amp..];
+        let decoded = tail.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &tail[1..end];
+            let ch = match entity {
+                "amp"  => Some('&'),
+                "lt"   => Some('<'),
+                "gt"   => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix('#')
+                    .and_then(|n| match n.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None      => n.parse::<u32>().ok(),
+                    })
+                    .and_then(char::from_u32),
+            }?;
+            Some((ch, end + 1))
+        });
+        match decoded {
+            Some((ch, len)) => {
+                out.push(ch);
+                rest = &tail[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Remove `(...)` / `[...]` groups whose text contains any of `tags`
+/// (case-insensitive), e.g. "Song (Official Video) [4K]" → "Song", then
+/// every match of the `patterns` (see `glob_match_at`).
+#[must_use]
+pub fn strip_title_tags(title: &str, tags: &[String], patterns: &[String]) -> String {
+    let tags: Vec<String> = tags.iter().map(|t| t.to_lowercase()).collect();
+    let mut out = String::with_capacity(title.len());
+    let mut rest = title;
+    while let Some(open) = rest.find(['(', '[']) {
+        let close_ch = if rest[open..].starts_with('(') { ')' } else { ']' };
+        let Some(len) = rest[open..].find(close_ch) else { break };
+        let group = &rest[open + 1..open + len];
+        let lower = group.to_lowercase();
+        out.push_str(&rest[..open]);
+        if !tags.iter().any(|t| lower.contains(t.as_str())) {
+            out.push_str(&rest[open..=open + len]);
+        }
+        rest = &rest[open + len + 1..];
+    }
+    out.push_str(rest);
+
+    let mut chars: Vec<char> = out.chars().collect();
+    for pattern in patterns.iter().filter(|p| !p.is_empty()) {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut start = 0;
+        while start < chars.len() {
+            match glob_match_at(&pattern, &chars, start) {
+                Some(end) => {
+                    chars.drain(start..end);
+                }
+                None => start += 1,
+            }
+        }
+    }
+    let out: String = chars.into_iter().collect();
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+        .trim_end_matches([' ', '-', '|'])
+        .to_string()
+}
+
+/// End of the longest non-empty match of `pattern` in `text` starting at
+/// `start`.  `*` matches any run of characters, `?` any one, anything else
+/// itself, ignoring case.
+fn glob_match_at(pattern: &[char], text: &[char], start: usize) -> Option<usize> {
+    let same = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+    // reach[k]: the pattern so far can end just before text[start + k].
+    let len = text.len() - start;
+    let mut reach = vec![false; len + 1];
+    reach[0] = true;
+    for &p in pattern {
+        let mut next = vec![false; len + 1];
+        match p {
+            '*' => {
+                let mut seen = false;
+                for k in 0..=len {
+                    seen |= reach[k];
+                    next[k] = seen;
+                }
+            }
+            _ => {
+                for k in 0..len {
+                    next[k + 1] = reach[k] && (p == '?' || same(p, text[start + k]));
+                }
+            }
+        }
+        reach = next;
+    }
+    (1..=len).rev().find(|&k| reach[k]).map(|k| start + k)
+}
+
+/// Whether a `media_strip_patterns` entry was probably written as a regular
+/// expression (anchors, `.*`, escapes, alternation), which a glob matches
+/// only literally.
+#[must_use]
+pub fn looks_like_regex(pattern: &str) -> bool {
+    let alternation = pattern
+        .split('(')
+        .skip(1)
+        .any(|group| group.split(')').next().is_some_and(|inner| inner.contains('|')));
+    alternation
+        || pattern.starts_with('^')
+        || (pattern.ends_with('$') && pattern.len() > 1)
+        || ["\\", ".*", ".+", "(?", "[^"].iter().any(|re| pattern.contains(re))
+}
+
+/// Collapse a joined multi-artist string to at most two names plus "+N".
+#[must_use]
+pub fn cap_artists(raw: &str) -> String {
+    let mut names: Vec<&str> = Vec::new();
+    for name in raw.split([',', ';']).map(str::trim).filter(|n| !n.is_empty()) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    match names.len() {
+        0..=2 => names.join(", "),
+        n     => format!("{} +{}", names[..2].join(", "), n - 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn decodes_named_and_numeric_entities() {
+        assert_eq!(decode_entities("Simon &amp; Garfunkel"), "Simon & Garfunkel");
+        assert_eq!(decode_entities("&quot;Hey&quot; &lt;3 &apos;em&gt;"), "\"Hey\" <3 'em>");
+        assert_eq!(decode_entities("Caf&#233; &#x2764;&#X1F3B5;"), "Café ❤🎵");
+        assert_eq!(decode_entities("Tom &amp;amp; Jerry"), "Tom &amp; Jerry");
+    }
+
+    #[test]
+    fn leaves_stray_ampersands_alone() {
+        assert_eq!(decode_entities("Rock & Roll"), "Rock & Roll");
+        assert_eq!(decode_entities("R&B; soul"), "R&B; soul");
+        assert_eq!(decode_entities("&nbsp;x &#xZZ; &#1114112; end &"), "&nbsp;x &#xZZ; &#1114112; end &");
+        assert_eq!(decode_entities("a &amp"), "a &amp");
+    }
+
+    #[test]
+    fn strips_tagged_groups_and_keeps_the_rest() {
+        let tags = strings(&["official", "lyric", "remaster", "4k"]);
+        assert_eq!(strip_title_tags("Song (Official Video) [4K]", &tags, &[]), "Song");
+        assert_eq!(strip_title_tags("Intro (Live) [Remastered 2011] -", &tags, &[]), "Intro (Live)");
+        assert_eq!(strip_title_tags("Track  (LYRIC VIDEO)  |", &tags, &[]), "Track");
+        assert_eq!(strip_title_tags("Unclosed (official", &tags, &[]), "Unclosed (official");
+        assert_eq!(strip_title_tags("東京 (Official Audio)", &tags, &[]), "東京");
+        assert_eq!(strip_title_tags("Song (Official Video)", &[], &[]), "Song (Official Video)");
+    }
+
+    #[test]
+    fn strips_pattern_matches() {
+        let patterns = strings(&["(feat. *)", "| * records", "- ??? remix"]);
+        assert_eq!(
+            strip_title_tags("Song (Feat. Somebody) | Big Records", &[], &patterns),
+            "Song"
+        );
+        assert_eq!(strip_title_tags("Tune - DnB Remix", &[], &patterns), "Tune");
+        assert_eq!(strip_title_tags("Tune - Remix", &[], &patterns), "Tune - Remix");
+        assert_eq!(strip_title_tags("ÉTÉ (feat. Ñandú)", &[], &patterns), "ÉTÉ");
+        assert_eq!(strip_title_tags("a-b-a-b", &[], &strings(&["-b"])), "a-a");
+    }
+
+    #[test]
+    fn flags_patterns_written_as_regexes() {
+        for re in [r"\s*\(Official.*\)", "^Live: ", r"- Topic$", "(?i)remaster", "(lyrics|video)"] {
+            assert!(looks_like_regex(re), "{re}");
+        }
+        for glob in ["(feat. *)", "| * Records", "[4K]", "? remix", "$", "Live @ *"] {
+            assert!(!looks_like_regex(glob), "{glob}");
+        }
+    }
+
+    #[test]
+    fn caps_artists_at_two_names() {
+        assert_eq!(cap_artists(""), "");
+        assert_eq!(cap_artists("Daft Punk"), "Daft Punk");
+        assert_eq!(cap_artists("Daft Punk, Pharrell Williams"), "Daft Punk, Pharrell Williams");
+        assert_eq!(cap_artists("A;B, C ,D"), "A, B +2");
+        assert_eq!(cap_artists("A, A, B, ,"), "A, B");
+        assert_eq!(cap_artists(" , ;"), "");
+    }
+}
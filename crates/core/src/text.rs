@@ -0,0 +1,104 @@
+/// The first `max` characters of `s`, borrowed.  Counts `char`s rather than
+/// bytes, so multi-byte text (accents, CJK, emoji) is never cut mid-character.
+#[must_use]
+pub fn truncate_chars(s: &str, max: usize) -> &str {
+    match s.char_indices().nth(max) {
+        Some((cut, _)) => &s[..cut],
+        None           => s,
+    }
+}
+
+/// Truncate `s` to at most `max` characters, appending `ellipsis` when cut.
+///
+/// The cut is made at the last whitespace before the limit so words are never
+/// split in half.  Strings without a usable break point (e.g. CJK titles, one
+/// very long word) fall back to a plain character cut.  `max` counts the
+/// ellipsis, so the result never exceeds `max` characters.
+#[must_use]
+pub fn truncate_smart(s: &str, max: usize, ellipsis: &str) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+
+    let budget = max.saturating_sub(ellipsis.chars().count());
+    if budget == 0 {
+        return truncate_chars(ellipsis, max).to_string();
+    }
+
+    let head = truncate_chars(s, budget);
+
+    // Only honour a word break if it keeps at least half the budget —
+    // otherwise "a verylongword…" would collapse to "a…".
+    let word_end = head
+        .char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .map(|(i, _)| i)
+        .next_back()
+        .filter(|&i| head[..i].chars().count() * 2 >= budget);
+
+    let kept = match word_end {
+        Some(i) => head[..i].trim_end(),
+        None    => head.trim_end(),
+    };
+    format!("{kept}{ellipsis}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_strings_are_returned_unchanged() {
+        assert_eq!(truncate_smart("", 10, "…"), "");
+        assert_eq!(truncate_smart("Song", 10, "…"), "Song");
+        assert_eq!(truncate_smart("exactly10!", 10, "…"), "exactly10!");
+    }
+
+    #[test]
+    fn cuts_at_a_word_break_and_counts_the_ellipsis() {
+        let out = truncate_smart("The quick brown fox jumps", 12, "…");
+        assert_eq!(out, "The quick…");
+        assert!(out.chars().count() <= 12);
+    }
+
+    #[test]
+    fn long_single_word_falls_back_to_a_character_cut() {
+        assert_eq!(truncate_smart("Supercalifragilistic", 8, "…"), "Superca…");
+    }
+
+    #[test]
+    fn cjk_is_cut_by_character_not_byte() {
+        let out = truncate_smart("東京事変の新しいアルバム", 6, "…");
+        assert_eq!(out, "東京事変の…");
+        assert_eq!(out.chars().count(), 6);
+    }
+
+    #[test]
+    fn limit_smaller_than_the_ellipsis() {
+        assert_eq!(truncate_smart("abcdef", 2, "..."), "..");
+        assert_eq!(truncate_smart("abcdef", 0, "…"), "");
+    }
+
+    #[test]
+    fn truncate_chars_counts_characters_not_bytes() {
+        assert_eq!(truncate_chars("café au lait", 4), "café");
+        assert_eq!(truncate_chars("東京事変", 2), "東京");
+        assert_eq!(truncate_chars("🎵🎶🎸", 1), "🎵");
+        // A flag is two code points; it may be split, but never mid-byte.
+        assert_eq!(truncate_chars("🇯🇵 Tokyo", 1), "🇯");
+        assert_eq!(truncate_chars("naïve", 10), "naïve");
+        assert_eq!(truncate_chars("", 3), "");
+    }
+
+    #[test]
+    fn multi_byte_input_never_panics() {
+        let inputs = ["🎵 Song – Artist", "東京事変の新しいアルバム", "e\u{301}e\u{301}e\u{301}", "👨‍👩‍👧 family"];
+        for s in inputs {
+            for max in 0..=s.chars().count() + 1 {
+                let cut = truncate_chars(s, max);
+                assert!(s.starts_with(cut));
+                assert!(truncate_smart(s, max, "…").chars().count() <= max);
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/// Which multiplier byte sizes are shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteUnits {
+    /// Powers of 1024, shown with the compact `K`/`M`/`G`/`T` suffixes.
+    #[default]
+    Iec,
+    /// Powers of 1000, shown as `kB`/`MB`/`GB`/`TB`.
+    Si,
+}
+
+impl ByteUnits {
+    /// Parse a config value: `"si"` selects [`ByteUnits::Si`], anything else IEC.
+    pub fn from_config(s: &str) -> Self {
+        if s.eq_ignore_ascii_case("si") { Self::Si } else { Self::Iec }
+    }
+}
+
+const IEC_SUFFIXES: [&str; 5] = ["B", "K", "M", "G", "T"];
+const SI_SUFFIXES:  [&str; 5] = ["B", "kB", "MB", "GB", "TB"];
+const BIT_SUFFIXES: [&str; 5] = ["b", "kb", "Mb", "Gb", "Tb"];
+
+/// Format a byte count, e.g. `1.5G` (IEC) or `1.6GB` (SI).
+#[must_use]
+pub fn format_bytes(bytes: u64, units: ByteUnits) -> String {
+    match units {
+        ByteUnits::Iec => scaled(bytes as f64, 1024.0, &IEC_SUFFIXES),
+        ByteUnits::Si  => scaled(bytes as f64, 1000.0, &SI_SUFFIXES),
+    }
+}
+
+/// Format a per-second byte rate as bits, always in SI multiples (`12Mb`)
+/// as network speeds are conventionally quoted.
+#[must_use]
+pub fn format_bits(bytes: u64) -> String {
+    scaled(bytes as f64 * 8.0, 1000.0, &BIT_SUFFIXES)
+}
+
+/// Swap the decimal point in already-formatted text for `sep` (`"1.5G"` →
+/// `"1,5G"`).  Only a `.` between two digits is replaced, so dotted names
+/// and trailing periods are left alone.
+#[must_use]
+pub fn localize_decimal(s: String, sep: char) -> String {
+    if sep == '.' || !s.contains('.') {
+        return s;
+    }
+    let chars: Vec<char> = s.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let between_digits = i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+            if c == '.' && between_digits { sep } else { c }
+        })
+        .collect()
+}
+
+/// Scale `value` to the largest unit below `base`.  There's one decimal from
+/// the giga step upwards, and for single-digit kilo and mega values ("1.5M"),
+/// none otherwise.  If rounding would print the base itself ("1024K"), the
+/// next unit up is used instead ("1.0M").
+fn scaled(value: f64, base: f64, suffixes: &[&str; 5]) -> String {
+    let decimals = |v: f64, idx: usize| -> usize {
+        if idx >= 3 || (idx >= 1 && (v * 10.0).round() < 100.0) { 1 } else { 0 }
+    };
+    let rounds_to = |v: f64, idx: usize| {
+        let f = if decimals(v, idx) == 1 { 10.0 } else { 1.0 };
+        (v * f).round() / f
+    };
+
+    let mut idx = 0;
+    let mut v = value;
+    while idx < suffixes.len() - 1 && rounds_to(v, idx) >= base {
+        v /= base;
+        idx += 1;
+    }
+    format!("{v:.prec$}{}", suffixes[idx], prec = decimals(v, idx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes_in_both_unit_systems() {
+        let cases: [(u64, &str, &str); 12] = [
+            (0,                      "0B",     "0B"),
+            (999,                    "999B",   "999B"),
+            (1_000,                  "1000B",  "1.0kB"),
+            (1_024,                  "1.0K",   "1.0kB"),
+            (1_536,                  "1.5K",   "1.5kB"),
+            (10_240,                 "10K",    "10kB"),
+            (1_048_575,              "1.0M",   "1.0MB"),
+            (1_572_864,              "1.5M",   "1.6MB"),
+            (9_961_472,              "9.5M",   "10MB"),
+            (512 * 1_048_576,        "512M",   "537MB"),
+            (3 * 1_073_741_824 / 2,  "1.5G",   "1.6GB"),
+            (2 * 1_099_511_627_776,  "2.0T",   "2.2TB"),
+        ];
+        for (bytes, iec, si) in cases {
+            assert_eq!(format_bytes(bytes, ByteUnits::Iec), iec, "{bytes} IEC");
+            assert_eq!(format_bytes(bytes, ByteUnits::Si), si, "{bytes} SI");
+        }
+    }
+
+    #[test]
+    fn formats_rates_as_si_bits() {
+        assert_eq!(format_bits(0), "0b");
+        assert_eq!(format_bits(100), "800b");
+        assert_eq!(format_bits(125), "1.0kb");
+        assert_eq!(format_bits(187_500), "1.5Mb");
+        assert_eq!(format_bits(1_500_000), "12Mb");
+        assert_eq!(format_bits(125_000_000), "1.0Gb");
+    }
+
+    #[test]
+    fn unit_is_promoted_when_rounding_reaches_the_base() {
+        assert_eq!(format_bytes(1_023, ByteUnits::Iec), "1023B");
+        assert_eq!(format_bytes(1_048_000, ByteUnits::Iec), "1023K");
+        assert_eq!(format_bytes(10_189, ByteUnits::Iec), "10K");
+        assert_eq!(format_bytes(999_999, ByteUnits::Si), "1.0MB");
+    }
+
+    #[test]
+    fn config_value_selects_units() {
+        assert_eq!(ByteUnits::from_config("SI"), ByteUnits::Si);
+        assert_eq!(ByteUnits::from_config("iec"), ByteUnits::Iec);
+        assert_eq!(ByteUnits::from_config(""), ByteUnits::Iec);
+    }
+
+    #[test]
+    fn localizes_only_decimal_points() {
+        assert_eq!(localize_decimal("1.5G".into(), ','), "1,5G");
+        assert_eq!(localize_decimal("1.5G".into(), '.'), "1.5G");
+        assert_eq!(localize_decimal("12.0 / 15.5 GB".into(), ','), "12,0 / 15,5 GB");
+        assert_eq!(localize_decimal("v1.2.3".into(), ','), "v1,2,3");
+        assert_eq!(localize_decimal("eth0.".into(), ','), "eth0.");
+        assert_eq!(localize_decimal(".5 and 5.".into(), ','), ".5 and 5.");
+        assert_eq!(localize_decimal("wlan.home".into(), ','), "wlan.home");
+        assert_eq!(localize_decimal("12%".into(), ','), "12%");
+    }
+}
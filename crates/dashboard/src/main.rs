@@ -2,11 +2,24 @@
 //!
 //! Launch with a Hyprland keybind:
 //!   `bind = SUPER, D, exec, bar-dashboard`
-//! Press Escape or click the dim background to dismiss.
+//! Press Escape or click the dim background to dismiss.  `/` or Ctrl+K
+//! opens the command palette; `bar-dashboard --palette` starts with it open.
 
-use bar_config::{default_path, load as load_config, schema::DashboardConfig};
-use bar_theme::Theme;
-use futures::channel::mpsc::Sender;
+use bar_config::{
+    default_path, load as load_config, load_with_sources, write_default,
+    schema::{CardConfig, DashConfig, DashboardConfig, PaletteEntry},
+    ConfigWatcher,
+};
+use bar_core::{
+    devices::{parse_upower_dump, parse_wireless, peripheral_event, Peripheral, WifiSignal},
+    format_bits, format_bytes,
+    layout::grid_layout,
+    localize_decimal,
+    media::{cap_artists, decode_entities, looks_like_regex, strip_title_tags},
+    truncate_smart, ByteUnits,
+};
+use bar_theme::{Color as ThemeColor, Theme};
+use futures::{channel::mpsc::Sender, SinkExt};
 use iced::{
     widget::{canvas, column, container, row, stack, text},
     Alignment, Background, Border, Color, Element, Font, Length, Subscription, Task,
@@ -17,12 +30,60 @@ use iced_layershell::{
     settings::{LayerShellSettings, Settings},
     to_layer_message,
 };
-use std::{collections::VecDeque, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 // ── Entry point ───────────────────────────────────────────────────────────────
 
+/// Config loaded once at startup and shared by `main`, `Dashboard::new` and
+/// `sys_stream`, so the file is only read and parsed once.
+static STARTUP_CONFIG: OnceLock<DashConfig> = OnceLock::new();
+/// Process start, for the startup timing log.
+static STARTED_AT: OnceLock<Instant> = OnceLock::new();
+/// There was no config file at startup, so the defaults were written out.
+static FIRST_RUN: AtomicBool = AtomicBool::new(false);
+
+fn startup_config() -> &'static DashConfig {
+    STARTUP_CONFIG.get_or_init(|| {
+        let path = default_path();
+        if !path.exists() {
+            match write_default(&path, &DashConfig::default()) {
+                Ok(()) => {
+                    tracing::info!(
+                        "No config found; wrote defaults to '{}' — edit it to customise the dashboard.",
+                        path.display()
+                    );
+                    FIRST_RUN.store(true, Ordering::Relaxed);
+                }
+                Err(e) => tracing::warn!(
+                    "Config file not found at '{}' and could not create it ({e}); using defaults.",
+                    path.display()
+                ),
+            }
+        }
+        load_config(&path).unwrap_or_else(|e| {
+            tracing::error!("{e}; starting with the default config");
+            DashConfig::default()
+        })
+    })
+}
+
+fn since_start() -> Duration {
+    STARTED_AT.get().map(Instant::elapsed).unwrap_or_default()
+}
+
 fn main() -> iced_layershell::Result {
-    let config = load_config(default_path()).unwrap_or_default();
+    STARTED_AT.get_or_init(Instant::now);
+    let config = startup_config();
+    tracing::info!("startup: config loaded after {:?}", since_start());
     if !config.dashboard.enabled {
         return Ok(());
     }
@@ -42,8 +103,8 @@ fn main() -> iced_layershell::Result {
             default_font,
             layer_settings: LayerShellSettings {
                 anchor: Anchor::Top | Anchor::Bottom | Anchor::Left | Anchor::Right,
-                layer:  Layer::Overlay,
-                exclusive_zone: -1,
+                layer:  parse_layer(&config.dashboard.layer),
+                exclusive_zone: config.dashboard.exclusive_zone.clamp(-1, 0),
                 keyboard_interactivity: KeyboardInteractivity::OnDemand,
                 ..Default::default()
             },
@@ -52,6 +113,20 @@ fn main() -> iced_layershell::Result {
         .run()
 }
 
+/// `dashboard.layer` as a layer-shell layer; unknown values mean overlay.
+fn parse_layer(name: &str) -> Layer {
+    match name.to_ascii_lowercase().as_str() {
+        "top"        => Layer::Top,
+        "bottom"     => Layer::Bottom,
+        "background" => Layer::Background,
+        "overlay"    => Layer::Overlay,
+        other => {
+            tracing::warn!("Unknown dashboard.layer `{other}`; using overlay");
+            Layer::Overlay
+        }
+    }
+}
+
 // ── System snapshot ───────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Default)]
@@ -66,16 +141,32 @@ struct DashSnapshot {
     net_iface:        String,
     net_rx_bps:       u64,
     net_tx_bps:       u64,
+    /// Wi-Fi signal level of `net_iface` from `/proc/net/wireless`; `None`
+    /// on wired interfaces or drivers that report no level.
+    net_signal_dbm:   Option<i32>,
+    /// Approximate signal quality in percent, from the level or, failing
+    /// that, the link quality.
+    net_signal_pct:   Option<u8>,
     volume:           Option<f32>,
     volume_muted:     bool,
+    /// Active output of the default sink; only read when `volume_show_port` is on.
+    audio_port:       Option<AudioPort>,
     brightness:       Option<u8>,
     battery_pct:      Option<u8>,
     battery_charging: bool,
+    /// The battery couldn't be read this time (as opposed to there being
+    /// none); merge_snapshot keeps the previous reading.
+    battery_failed:   bool,
     uptime_secs:      u64,
     temp_celsius:     Option<f32>,
     media_title:      Option<String>,
     media_artist:     Option<String>,
     media_playing:    bool,
+    /// When `media_playing` last changed, as seen by this dashboard (set in
+    /// merge_snapshot).
+    media_state_since: Option<Instant>,
+    /// Every MPRIS player currently running (`playerctl -l`).
+    media_players:    Vec<String>,
     update_count:     Option<u32>,
     load_1:           f32,
     load_5:           f32,
@@ -86,13 +177,210 @@ struct DashSnapshot {
     gpu_mem_total:    Option<u64>,
     bt_connected:     bool,
     bt_device_name:   Option<String>,
+    /// Mice, keyboards and headsets reporting a battery level to UPower;
+    /// only read while a `"peripherals"` card is in the layout.
+    peripherals:      Vec<Peripheral>,
     weather_text:     String,
+    /// Cumulative user+system CPU ticks consumed by this process.
+    self_cpu_ticks:   u64,
+    /// Resident set size of this process in bytes.
+    self_rss:         u64,
+    /// This process's CPU usage since the previous snapshot (derived in merge_snapshot).
+    self_cpu_pct:     f32,
+    /// When this snapshot was taken; `None` until the first read completes.
+    sampled_at:       Option<Instant>,
+    /// How long the read itself took.
+    read_time:        Duration,
+    /// Taken while every output was off, so audio and media weren't read.
+    asleep:           bool,
     // Rolling history buffers — capped at 60 samples (~2 min at 2s poll)
     cpu_history:      VecDeque<f32>,
     net_rx_history:   VecDeque<f32>,
 }
 
-async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
+// Derived readings, so cards, templates and alerts compute them one way.
+impl DashSnapshot {
+    /// Memory in use, 0–1.
+    fn ram_frac(&self) -> f32 {
+        ratio(self.ram_used, self.ram_total)
+    }
+
+    /// Swap in use, 0–1.
+    fn swap_frac(&self) -> f32 {
+        ratio(self.swap_used, self.swap_total)
+    }
+
+    /// Root filesystem in use, 0–1.
+    fn disk_frac(&self) -> f32 {
+        ratio(self.disk_used, self.disk_total)
+    }
+
+    /// The primary interface and its Wi-Fi signal in percent, or `None`
+    /// while there is no network.
+    fn network(&self) -> Option<(&str, Option<u8>)> {
+        (!self.net_iface.is_empty()).then_some((self.net_iface.as_str(), self.net_signal_pct))
+    }
+
+    /// Charge percentage and whether it is charging, or `None` without a battery.
+    fn battery(&self) -> Option<(u8, bool)> {
+        self.battery_pct.map(|pct| (pct, self.battery_charging))
+    }
+}
+
+/// `used / total`, or 0 when `total` is 0 (not read yet, or no swap).
+fn ratio(used: u64, total: u64) -> f32 {
+    if total == 0 { 0.0 } else { used as f32 / total as f32 }
+}
+
+/// Config-derived settings the snapshot reader needs.  Cloned into the
+/// background stream once, so the reader never touches the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct ReadOptions {
+    weather_location: String,
+    media_strip_tags: Vec<String>,
+    media_strip_patterns: Vec<String>,
+    /// Read the default sink's active port (`theme.volume_show_port`).
+    audio_port:       bool,
+    /// A `"peripherals"` card is in the layout, so follow UPower's devices.
+    peripherals:      bool,
+    disk_interval:    Duration,
+    updates_interval: Duration,
+    weather_interval: Duration,
+}
+
+impl ReadOptions {
+    fn from_config(config: &DashConfig) -> Self {
+        for pattern in config.media_strip_patterns.iter().filter(|p| looks_like_regex(p)) {
+            tracing::warn!(
+                "media_strip_patterns entry '{pattern}' looks like a regular expression; \
+                 these are globs (`*` and `?` only), so the rest is matched literally"
+            );
+        }
+        Self {
+            weather_location: config.weather_location.clone(),
+            media_strip_tags: config.media_strip_tags.clone(),
+            media_strip_patterns: config.media_strip_patterns.clone(),
+            audio_port:       config.theme.volume_show_port,
+            peripherals:      config.dashboard.items.iter()
+                .any(|c| c.kind == "peripherals" || c.children.iter().any(|k| k.kind == "peripherals")),
+            disk_interval:    Duration::from_secs(config.disk_interval_secs.max(2)),
+            updates_interval: Duration::from_secs(config.updates_interval_secs.max(2)),
+            weather_interval: Duration::from_secs(config.weather_interval_secs.max(2)),
+        }
+    }
+
+    /// The slow sources to run, each with its interval.
+    fn slow_sources(&self) -> Vec<(SlowSource, Duration)> {
+        let mut sources = vec![
+            (SlowSource::Disk, self.disk_interval),
+            (SlowSource::Updates, self.updates_interval),
+        ];
+        if !self.weather_location.is_empty() {
+            sources.push((SlowSource::Weather(self.weather_location.clone()), self.weather_interval));
+        }
+        sources
+    }
+}
+
+/// A slow-changing source, read on its own interval by `slow_stream` rather
+/// than with every snapshot, so a long `checkupdates` run or weather lookup
+/// never holds up the CPU, memory and audio readings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SlowSource {
+    Disk,
+    Updates,
+    /// The weather for this location.
+    Weather(String),
+}
+
+impl SlowSource {
+    /// Read the source once.  `None` (a failed read) keeps the last value.
+    async fn read(&self) -> Option<Slice> {
+        match self {
+            Self::Disk => {
+                let (used, total) = tokio::task::spawn_blocking(read_disk).await.ok()?;
+                Some(Slice::Disk { used, total })
+            }
+            Self::Updates           => read_updates().await.map(Slice::Updates),
+            Self::Weather(location) => Some(Slice::Weather(read_weather(location.clone()).await)),
+        }
+    }
+}
+
+/// One slow source's reading (or, for peripherals, UPower's latest device
+/// list), applied over the matching snapshot fields.
+#[derive(Debug, Clone)]
+enum Slice {
+    Disk { used: u64, total: u64 },
+    Updates(u32),
+    Weather(String),
+    Peripherals(Vec<Peripheral>),
+}
+
+/// Capped exponential backoff with jitter, for retrying something that
+/// isn't answering.  The random part keeps retries from several clients
+/// from lining up.
+struct Backoff {
+    base:     Duration,
+    max:      Duration,
+    failures: u32,
+}
+
+impl Backoff {
+    fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max, failures: 0 }
+    }
+
+    /// Delay before the next attempt after another failure: `base`, doubled
+    /// for each earlier failure up to `max`, then a random point in the
+    /// upper half of that.
+    fn next(&mut self) -> Duration {
+        let cap = self.base.saturating_mul(1 << self.failures.min(16)).min(self.max);
+        self.failures = self.failures.saturating_add(1);
+        cap / 2 + cap.mul_f64(jitter() / 2.0)
+    }
+
+    /// Start again from `base` after a success.
+    fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+/// A random fraction in `[0, 1)`, from the standard library's randomly
+/// keyed hasher — enough to spread retries without another dependency.
+fn jitter() -> f64 {
+    use std::hash::{BuildHasher, RandomState};
+    (RandomState::new().hash_one(Instant::now()) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Tracks when a periodic check was last run.
+struct Ticker {
+    every: Duration,
+    last:  Option<Instant>,
+}
+
+impl Ticker {
+    fn new(every: Duration) -> Self {
+        Self { every, last: None }
+    }
+
+    /// True (and restarts the interval) when the check should run now.
+    fn due(&mut self, now: Instant) -> bool {
+        let due = match self.last {
+            Some(last) => now.duration_since(last) >= self.every,
+            None       => true,
+        };
+        if due {
+            self.last = Some(now);
+        }
+        due
+    }
+}
+
+/// Take a snapshot.  With `asleep` (every output is off) the audio and media
+/// reads are skipped; merge_snapshot keeps their last values.
+async fn read_sys_snapshot(opts: ReadOptions, asleep: bool) -> DashSnapshot {
+    let read_start = Instant::now();
     // Heavy sysinfo work in a blocking thread — CPU needs 150ms between samples.
     // Heavy sysinfo work in a blocking thread — CPU needs 150ms between samples.
     // Split into two smaller tuples (Rust Default only supports tuples up to 12).
@@ -102,11 +390,10 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
         ram_total:  u64,
         swap_used:  u64,
         swap_total: u64,
-        disk_used:  u64,
-        disk_total: u64,
         net_iface:  String,
         net_rx_bps: u64,
         net_tx_bps: u64,
+        net_signal: WifiSignal,
         uptime_secs: u64,
         temp_celsius: Option<f32>,
         load_1:     f32,
@@ -114,7 +401,7 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
         load_15:    f32,
     }
 
-    let info = tokio::task::spawn_blocking(|| {
+    let info = tokio::task::spawn_blocking(move || {
         use sysinfo::System;
         let mut sys = System::new();
         sys.refresh_cpu_all();
@@ -131,12 +418,6 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
 
         let load = System::load_average();
 
-        let disks = sysinfo::Disks::new_with_refreshed_list();
-        let (disk_used, disk_total) = disks.iter()
-            .find(|d| d.mount_point() == std::path::Path::new("/"))
-            .map(|d| (d.total_space() - d.available_space(), d.total_space()))
-            .unwrap_or((0, 1));
-
         // Network: sample twice with a short delay to get rate
         let mut nets = sysinfo::Networks::new_with_refreshed_list();
         std::thread::sleep(Duration::from_millis(200));
@@ -149,6 +430,10 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
             })
             .map(|(n, d)| (n.clone(), d.received(), d.transmitted()))
             .unwrap_or_else(|| (String::new(), 0, 0));
+        let net_signal = std::fs::read_to_string("/proc/net/wireless")
+            .ok()
+            .and_then(|raw| parse_wireless(&raw, &net_iface))
+            .unwrap_or_default();
 
         let comps = sysinfo::Components::new_with_refreshed_list();
         let temp = comps.iter()
@@ -162,8 +447,7 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
         SysInfo {
             cpu_pct, ram_used, ram_total,
             swap_used, swap_total,
-            disk_used, disk_total,
-            net_iface, net_rx_bps, net_tx_bps,
+            net_iface, net_rx_bps, net_tx_bps, net_signal,
             uptime_secs: uptime, temp_celsius: temp,
             load_1: load.one as f32, load_5: load.five as f32, load_15: load.fifteen as f32,
         }
@@ -172,8 +456,8 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
     .unwrap_or_else(|_| SysInfo {
         cpu_pct: 0.0, ram_used: 0, ram_total: 0,
         swap_used: 0, swap_total: 0,
-        disk_used: 0, disk_total: 1,
         net_iface: String::new(), net_rx_bps: 0, net_tx_bps: 0,
+        net_signal: WifiSignal::default(),
         uptime_secs: 0, temp_celsius: None,
         load_1: 0.0, load_5: 0.0, load_15: 0.0,
     });
@@ -181,36 +465,34 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
     let SysInfo {
         cpu_pct, ram_used, ram_total,
         swap_used, swap_total,
-        disk_used, disk_total,
-        net_iface, net_rx_bps, net_tx_bps,
+        net_iface, net_rx_bps, net_tx_bps, net_signal,
         uptime_secs, temp_celsius,
         load_1, load_5, load_15,
     } = info;
 
     // Parallel async reads for everything else.
-    let (vol_out, bright, bat, title_out, artist_out, status_out, upd_out, gpu_out, bt_out, weather_out) = tokio::join!(
-        tokio::process::Command::new("wpctl")
-            .args(["get-volume", "@DEFAULT_AUDIO_SINK@"])
-            .output(),
+    let player = selected_player();
+    let want_port = opts.audio_port && !asleep;
+    let awake_output = |mut cmd: tokio::process::Command| async move {
+        if asleep { None } else { cmd.output().await.ok() }
+    };
+    let mut wpctl = tokio::process::Command::new("wpctl");
+    wpctl.args(["get-volume", "@DEFAULT_AUDIO_SINK@"]);
+    let (vol_out, audio_port, bright, bat, title_out, artist_out, status_out, players_out, gpu_out, bt_out) = tokio::join!(
+        awake_output(wpctl),
+        async move { if want_port { read_audio_port().await } else { None } },
         read_brightness(),
         tokio::task::spawn_blocking(read_battery),
-        tokio::process::Command::new("playerctl")
-            .args(["metadata", "--format", "{{title}}"])
-            .output(),
-        tokio::process::Command::new("playerctl")
-            .args(["metadata", "--format", "{{artist}}"])
-            .output(),
-        tokio::process::Command::new("playerctl")
-            .args(["status"])
-            .output(),
-        tokio::process::Command::new("checkupdates").output(),
+        awake_output(playerctl(player.as_deref(), &["metadata", "--format", "{{title}}"])),
+        awake_output(playerctl(player.as_deref(), &["metadata", "--format", "{{artist}}"])),
+        awake_output(playerctl(player.as_deref(), &["status"])),
+        awake_output(playerctl(None, &["--list-all"])),
         read_gpu(),
         read_bluetooth(),
-        read_weather(weather_location),
     );
 
     // Volume: "Volume: 0.60" or "Volume: 0.60 [MUTED]"
-    let (volume, volume_muted) = vol_out.ok()
+    let (volume, volume_muted) = vol_out
         .and_then(|o| {
             let s = String::from_utf8_lossy(&o.stdout).to_string();
             let muted = s.contains("[MUTED]");
@@ -219,74 +501,203 @@ async fn read_sys_snapshot(weather_location: String) -> DashSnapshot {
         })
         .unwrap_or((None, false));
 
-    let media_title = title_out.ok()
+    let media_title = title_out
         .filter(|o| o.status.success())
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .map(|o| {
+            let raw = decode_entities(String::from_utf8_lossy(&o.stdout).trim());
+            strip_title_tags(&raw, &opts.media_strip_tags, &opts.media_strip_patterns)
+        })
         .filter(|s| !s.is_empty());
 
-    let media_artist = artist_out.ok()
+    let media_artist = artist_out
         .filter(|o| o.status.success())
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .map(|o| cap_artists(&decode_entities(String::from_utf8_lossy(&o.stdout).trim())))
         .filter(|s| !s.is_empty());
 
-    let media_playing = status_out.ok()
+    let media_playing = status_out
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
         .map(|s| s == "Playing")
         .unwrap_or(false);
 
-    let (battery_pct, battery_charging) = bat.unwrap_or_default();
-
-    let update_count = upd_out.ok().map(|o| {
-        String::from_utf8_lossy(&o.stdout)
-            .lines()
-            .filter(|l| !l.trim().is_empty())
-            .count() as u32
-    });
+    let media_players: Vec<String> = players_out
+        .filter(|o| o.status.success())
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (battery_pct, battery_charging, battery_failed) = match bat {
+        Ok(Ok(BatteryRead::Reading { pct, charging })) => (Some(pct), charging, false),
+        Ok(Ok(BatteryRead::NoBattery)) => (None, false, false),
+        Ok(Err(e)) => {
+            tracing::debug!("Battery read failed, keeping the last value: {e}");
+            (None, false, true)
+        }
+        Err(e) => {
+            tracing::debug!("Battery read task failed, keeping the last value: {e}");
+            (None, false, true)
+        }
+    };
 
     let (gpu_percent, gpu_temp, gpu_mem_used, gpu_mem_total) = gpu_out;
 
     let (bt_connected, bt_device_name) = bt_out;
 
-    let weather_text = weather_out;
+    let (self_cpu_ticks, self_rss) = read_self_stats();
 
     DashSnapshot {
         cpu_pct, ram_used, ram_total,
         swap_used, swap_total,
-        disk_used, disk_total,
         net_iface, net_rx_bps, net_tx_bps,
-        volume, volume_muted, brightness: bright,
-        battery_pct, battery_charging, uptime_secs, temp_celsius,
-        media_title, media_artist, media_playing, update_count,
+        net_signal_dbm: net_signal.dbm(),
+        net_signal_pct: net_signal.pct(),
+        volume, volume_muted, audio_port, brightness: bright,
+        battery_pct, battery_charging, battery_failed, uptime_secs, temp_celsius,
+        media_title, media_artist, media_playing, media_players,
         load_1, load_5, load_15,
         gpu_percent, gpu_temp, gpu_mem_used, gpu_mem_total,
         bt_connected, bt_device_name,
-        weather_text,
+        self_cpu_ticks, self_rss,
+        self_cpu_pct: 0.0,
+        media_state_since: None,
+        sampled_at: Some(Instant::now()),
+        read_time: read_start.elapsed(),
+        asleep,
         // History buffers start empty — populated by Dashboard::merge_snapshot
         cpu_history: VecDeque::new(),
         net_rx_history: VecDeque::new(),
+        // Slow sources arrive separately — see slow_stream
+        ..Default::default()
     }
 }
 
-fn read_battery() -> (Option<u8>, bool) {
-    let dir = std::path::Path::new("/sys/class/power_supply");
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for e in entries.flatten() {
-            if e.file_name().to_string_lossy().to_uppercase().starts_with("BAT") {
-                let p = e.path();
-                let pct = std::fs::read_to_string(p.join("capacity"))
-                    .ok().and_then(|s| s.trim().parse::<u8>().ok());
-                let status = std::fs::read_to_string(p.join("status"))
-                    .ok().map(|s| s.trim().to_string()).unwrap_or_default();
-                let charging = matches!(status.as_str(), "Charging" | "Full");
-                return (pct, charging);
+/// Used and total bytes of the root filesystem.
+fn read_disk() -> (u64, u64) {
+    sysinfo::Disks::new_with_refreshed_list()
+        .iter()
+        .find(|d| d.mount_point() == std::path::Path::new("/"))
+        .map(|d| (d.total_space() - d.available_space(), d.total_space()))
+        .unwrap_or((0, 1))
+}
+
+// ── Metrics template ──────────────────────────────────────────────────────────
+
+/// Placeholder names already reported as unknown, so each is logged once.
+static UNKNOWN_PLACEHOLDERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Expand `{name}` placeholders in `template` using `lookup`.  Unknown names
+/// are left as-is (braces included) and logged the first time they're seen.
+fn expand_template(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out  = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+        let name = &after[..close];
+        match lookup(name) {
+            Some(value) => out.push_str(&value),
+            None => {
+                out.push_str(&rest[open..open + close + 2]);
+                if let Ok(mut seen) = UNKNOWN_PLACEHOLDERS.lock() {
+                    if !seen.iter().any(|s| s == name) {
+                        tracing::warn!("Unknown metrics placeholder {{{name}}}");
+                        seen.push(name.to_string());
+                    }
+                }
             }
         }
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// ── Media player selection ────────────────────────────────────────────────────
+
+/// Player every `playerctl` call targets; `None` lets playerctl pick.  Shared
+/// between the UI (which cycles it) and the snapshot reader.
+static MEDIA_PLAYER: Mutex<Option<String>> = Mutex::new(None);
+
+fn selected_player() -> Option<String> {
+    MEDIA_PLAYER.lock().ok().and_then(|p| p.clone())
+}
+
+fn select_player(name: Option<String>) {
+    if let Ok(mut p) = MEDIA_PLAYER.lock() {
+        *p = name;
     }
-    (None, false)
+}
+
+/// Build a `playerctl` command, pinned to `player` when one is given.
+fn playerctl(player: Option<&str>, args: &[&str]) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("playerctl");
+    if let Some(name) = player {
+        cmd.arg(format!("--player={name}"));
+    }
+    cmd.args(args);
+    cmd
+}
+
+/// Human-friendly player name: `"firefox.instance_1_42"` → `"firefox"`.
+fn player_label(name: &str) -> &str {
+    name.split(".instance").next().unwrap_or(name)
+}
+
+/// Result of a successful battery read.
+#[derive(Debug)]
+enum BatteryRead {
+    /// No `BAT*` power supply — a desktop, not an error.
+    NoBattery,
+    Reading { pct: u8, charging: bool },
+}
+
+/// Read the first `BAT*` power supply.  Failing to read one that exists
+/// (sysfs can be briefly busy) is an `Err`, so the caller can keep showing
+/// the last value instead of hiding the card.
+fn read_battery() -> std::io::Result<BatteryRead> {
+    let entries = match std::fs::read_dir("/sys/class/power_supply") {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BatteryRead::NoBattery),
+        Err(e) => return Err(e),
+    };
+    let Some(dir) = entries
+        .flatten()
+        .find(|e| e.file_name().to_string_lossy().to_uppercase().starts_with("BAT"))
+        .map(|e| e.path())
+    else {
+        return Ok(BatteryRead::NoBattery);
+    };
+
+    let pct = std::fs::read_to_string(dir.join("capacity"))?
+        .trim()
+        .parse::<u8>()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let status = std::fs::read_to_string(dir.join("status"))?;
+    let charging = matches!(status.trim(), "Charging" | "Full");
+    Ok(BatteryRead::Reading { pct, charging })
+}
+
+/// `true` when the kernel exposes at least one backlight device (laptop panels).
+fn has_backlight() -> bool {
+    std::fs::read_dir("/sys/class/backlight")
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
 }
 
 async fn read_brightness() -> Option<u8> {
+    if !has_backlight() {
+        return read_ddc_brightness().await;
+    }
     let dir = std::path::Path::new("/sys/class/backlight");
     if let Ok(mut entries) = tokio::fs::read_dir(dir).await {
         if let Ok(Some(e)) = entries.next_entry().await {
@@ -303,6 +714,122 @@ async fn read_brightness() -> Option<u8> {
     None
 }
 
+// ── Host identity ─────────────────────────────────────────────────────────────
+
+/// Login name, hostname and whether this session looks remote.  Read once at
+/// startup — none of it changes while the overlay is open.
+#[derive(Debug, Clone, Default)]
+struct HostInfo {
+    user:   String,
+    host:   String,
+    remote: bool,
+}
+
+impl HostInfo {
+    fn detect() -> Self {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+
+        let host = ["/proc/sys/kernel/hostname", "/etc/hostname"]
+            .iter()
+            .find_map(|p| {
+                std::fs::read_to_string(p).ok()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            })
+            .or_else(|| env("HOSTNAME"))
+            .unwrap_or_else(|| "localhost".to_string());
+        let user = env("USER").or_else(|| env("LOGNAME")).unwrap_or_default();
+        let remote = ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+            .iter()
+            .any(|key| env(key).is_some());
+
+        Self { user, host, remote }
+    }
+}
+
+// ── DDC/CI brightness (external monitors) ─────────────────────────────────────
+
+/// `ddcutil` takes around a second per call, so the last reading is cached and
+/// refreshed at most every [`DDC_POLL_INTERVAL`].  `None` = never read.
+static DDC_CACHE: Mutex<Option<(Option<u8>, Instant)>> = Mutex::new(None);
+/// Bumped on every slider move; a pending `setvcp` only runs if it is still current.
+static DDC_SET_GEN: AtomicU64 = AtomicU64::new(0);
+
+const DDC_POLL_INTERVAL: Duration = Duration::from_secs(30);
+const DDC_SET_DEBOUNCE:  Duration = Duration::from_millis(400);
+
+/// Read VCP feature 0x10 (luminance) via `ddcutil`, served from the cache when fresh.
+async fn read_ddc_brightness() -> Option<u8> {
+    let cached = *DDC_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((value, at)) = cached {
+        if at.elapsed() < DDC_POLL_INTERVAL {
+            return value;
+        }
+    }
+
+    // `--brief` prints e.g. "VCP 10 C 50 100" (feature, type, current, max).
+    let value = tokio::process::Command::new("ddcutil")
+        .args(["getvcp", "10", "--brief"])
+        .output()
+        .await
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| {
+            let s = String::from_utf8_lossy(&o.stdout).to_string();
+            let fields: Vec<&str> = s.split_whitespace().collect();
+            let cur = fields.get(3)?.parse::<u64>().ok()?;
+            let max = fields.get(4)?.parse::<u64>().ok()?;
+            (max > 0).then(|| ((cur as f64 / max as f64 * 100.0).round()) as u8)
+        });
+
+    *DDC_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some((value, Instant::now()));
+    value
+}
+
+/// Set DDC/CI brightness, collapsing a burst of slider moves into one `setvcp`.
+fn set_ddc_brightness(pct: u8) {
+    // Update the cache right away so the next poll doesn't snap the slider back.
+    *DDC_CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some((Some(pct), Instant::now()));
+
+    let generation = DDC_SET_GEN.fetch_add(1, Ordering::SeqCst) + 1;
+    tokio::spawn(async move {
+        tokio::time::sleep(DDC_SET_DEBOUNCE).await;
+        if DDC_SET_GEN.load(Ordering::SeqCst) != generation {
+            return; // superseded by a newer value
+        }
+        let _ = tokio::process::Command::new("ddcutil")
+            .args(["setvcp", "10", &pct.to_string()])
+            .output().await;
+    });
+}
+
+/// Read this process's cumulative CPU ticks and RSS from `/proc/self`.
+///
+/// `utime`/`stime` are fields 14 and 15 of `/proc/self/stat`; the command name
+/// in field 2 may contain spaces, so fields are counted after the closing `)`.
+fn read_self_stats() -> (u64, u64) {
+    const PAGE_SIZE: u64 = 4096;
+
+    let ticks = std::fs::read_to_string("/proc/self/stat")
+        .ok()
+        .and_then(|s| {
+            let rest = &s[s.rfind(')')? + 1..];
+            let fields: Vec<&str> = rest.split_whitespace().collect();
+            let utime = fields.get(11)?.parse::<u64>().ok()?;
+            let stime = fields.get(12)?.parse::<u64>().ok()?;
+            Some(utime + stime)
+        })
+        .unwrap_or(0);
+
+    let rss = std::fs::read_to_string("/proc/self/statm")
+        .ok()
+        .and_then(|s| s.split_whitespace().nth(1)?.parse::<u64>().ok())
+        .map(|pages| pages * PAGE_SIZE)
+        .unwrap_or(0);
+
+    (ticks, rss)
+}
+
 /// Try nvidia-smi first, then radeontop (AMD) for GPU stats.
 async fn read_gpu() -> (Option<f32>, Option<f32>, Option<u64>, Option<u64>) {
     // Try nvidia-smi
@@ -339,6 +866,270 @@ async fn read_gpu() -> (Option<f32>, Option<f32>, Option<u64>, Option<u64>) {
     (gpu_pct, gpu_temp, None, None)
 }
 
+/// Output the default audio sink is currently playing through.
+#[derive(Debug, Clone, PartialEq)]
+enum AudioPort {
+    Speakers,
+    Headphones,
+    Hdmi,
+    /// Bluetooth sink, with the device's description.
+    Bluetooth(String),
+}
+
+impl AudioPort {
+    fn icon(&self, nerd: bool) -> &'static str {
+        match (self, nerd) {
+            (Self::Speakers, true)      => "\u{f04c3}",
+            (Self::Headphones, true)    => "\u{f02cb}",
+            (Self::Hdmi, true)          => "\u{f0502}",
+            (Self::Bluetooth(_), true)  => "\u{f00b0}",
+            (Self::Speakers, false)     => "SPK",
+            (Self::Headphones, false)   => "HP",
+            (Self::Hdmi, false)         => "HDMI",
+            (Self::Bluetooth(_), false) => "BT",
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            Self::Speakers        => "Speakers",
+            Self::Headphones      => "Headphones",
+            Self::Hdmi            => "HDMI",
+            Self::Bluetooth(name) => name,
+        }
+    }
+}
+
+/// Find the default sink in `pactl list sinks` and classify its active port.
+async fn read_audio_port() -> Option<AudioPort> {
+    let (default_out, list_out) = tokio::join!(
+        tokio::process::Command::new("pactl").arg("get-default-sink").output(),
+        tokio::process::Command::new("pactl").args(["list", "sinks"]).output(),
+    );
+    let default = String::from_utf8_lossy(&default_out.ok()?.stdout).trim().to_string();
+    let list = String::from_utf8_lossy(&list_out.ok()?.stdout).to_string();
+
+    // Sinks are blocks starting with "Sink #N"; fields are indented "Key: value".
+    let block = list
+        .split("Sink #")
+        .find(|b| b.lines().any(|l| l.trim().strip_prefix("Name: ") == Some(default.as_str())))?;
+    let field = |key: &str| {
+        block.lines().find_map(|l| l.trim().strip_prefix(key).map(str::trim))
+    };
+
+    if default.starts_with("bluez") {
+        let name = field("Description:").unwrap_or("Bluetooth");
+        return Some(AudioPort::Bluetooth(name.to_string()));
+    }
+    let port = field("Active Port:").unwrap_or("").to_lowercase();
+    Some(if port.contains("hdmi") || port.contains("displayport") {
+        AudioPort::Hdmi
+    } else if port.contains("headphone") || port.contains("headset") {
+        AudioPort::Headphones
+    } else {
+        AudioPort::Speakers
+    })
+}
+
+// ── Wi-Fi picker ──────────────────────────────────────────────────────────────
+
+/// A nearby network as listed by the Wi-Fi backend.
+#[derive(Debug, Clone)]
+struct WifiNetwork {
+    ssid:    String,
+    /// Signal strength, 0–100.
+    signal:  u8,
+    secured: bool,
+    /// A saved connection exists, so connecting needs no password.
+    known:   bool,
+    active:  bool,
+}
+
+/// Scan results are reused for this long before reopening the picker rescans.
+const WIFI_CACHE_TTL: Duration = Duration::from_secs(30);
+const WIFI_SCAN_TIMEOUT: Duration = Duration::from_secs(15);
+const WIFI_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// First interface with a `wireless/` directory in sysfs.
+fn wireless_iface() -> Option<String> {
+    std::fs::read_dir("/sys/class/net").ok()?
+        .flatten()
+        .find(|e| e.path().join("wireless").exists())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+}
+
+/// Run a command with a timeout, returning stdout on success or a short
+/// error message (first stderr line) on failure.
+async fn run_with_timeout(prog: &str, args: &[&str], limit: Duration) -> Result<String, String> {
+    let out = tokio::time::timeout(limit, tokio::process::Command::new(prog).args(args).output())
+        .await
+        .map_err(|_| format!("{prog} timed out"))?
+        .map_err(|e| format!("{prog}: {e}"))?;
+    if out.status.success() {
+        Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+    } else {
+        let err = String::from_utf8_lossy(&out.stderr);
+        Err(err.lines().next().unwrap_or("command failed").trim().to_string())
+    }
+}
+
+async fn scan_wifi(backend: String) -> Result<Vec<WifiNetwork>, String> {
+    let mut nets = if backend == "iwd" { scan_wifi_iwd().await? } else { scan_wifi_nm().await? };
+    nets.sort_by(|a, b| b.active.cmp(&a.active).then(b.signal.cmp(&a.signal)));
+    Ok(nets)
+}
+
+/// Split one line of `nmcli -t` output, honouring `\:` escapes.
+fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    fields.last_mut().unwrap().push(next);
+                }
+            }
+            ':' => fields.push(String::new()),
+            c   => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+async fn scan_wifi_nm() -> Result<Vec<WifiNetwork>, String> {
+    let (list, known) = tokio::join!(
+        run_with_timeout(
+            "nmcli",
+            &["-t", "-f", "IN-USE,SSID,SIGNAL,SECURITY", "device", "wifi", "list", "--rescan", "yes"],
+            WIFI_SCAN_TIMEOUT,
+        ),
+        run_with_timeout("nmcli", &["-t", "-f", "NAME,TYPE", "connection", "show"], WIFI_SCAN_TIMEOUT),
+    );
+    let known: Vec<String> = known
+        .unwrap_or_default()
+        .lines()
+        .map(split_terse)
+        .filter(|f| f.get(1).is_some_and(|t| t == "802-11-wireless"))
+        .map(|mut f| f.swap_remove(0))
+        .collect();
+
+    let mut nets: Vec<WifiNetwork> = Vec::new();
+    for fields in list?.lines().map(split_terse) {
+        let [in_use, ssid, signal, security] = fields.as_slice() else { continue };
+        if ssid.is_empty() {
+            continue; // hidden network
+        }
+        let net = WifiNetwork {
+            ssid:    ssid.clone(),
+            signal:  signal.parse().unwrap_or(0),
+            secured: !security.is_empty() && security != "--",
+            known:   known.contains(ssid),
+            active:  in_use == "*",
+        };
+        // One row per SSID — keep the strongest access point
+        match nets.iter_mut().find(|n| n.ssid == net.ssid) {
+            Some(existing) if existing.signal < net.signal || net.active => *existing = net,
+            Some(_) => {}
+            None => nets.push(net),
+        }
+    }
+    Ok(nets)
+}
+
+/// Signal strength in bars, 0–3: 75% and up is 3, then 50% and 25%.
+fn signal_level(signal: u8) -> u8 {
+    match signal {
+        75.. => 3,
+        50.. => 2,
+        25.. => 1,
+        _    => 0,
+    }
+}
+
+/// Remove ANSI colour sequences from `iwctl` output.
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip to the end of the CSI sequence (a letter)
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() { break; }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Rows of an `iwctl` table: everything below the last `----` rule.
+fn iwctl_rows(raw: &str) -> Vec<String> {
+    let clean = strip_ansi(raw);
+    let lines: Vec<&str> = clean.lines().collect();
+    let start = lines.iter().rposition(|l| l.trim_start().starts_with("---")).map_or(0, |i| i + 1);
+    lines[start..].iter().map(|l| l.to_string()).filter(|l| !l.trim().is_empty()).collect()
+}
+
+async fn scan_wifi_iwd() -> Result<Vec<WifiNetwork>, String> {
+    let dev = wireless_iface().ok_or("no wireless interface found")?;
+    // `scan` only triggers the scan; give it a moment before listing.
+    let _ = run_with_timeout("iwctl", &["station", &dev, "scan"], WIFI_SCAN_TIMEOUT).await;
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let list_args = ["station", dev.as_str(), "get-networks"];
+    let (list, known) = tokio::join!(
+        run_with_timeout("iwctl", &list_args, WIFI_SCAN_TIMEOUT),
+        run_with_timeout("iwctl", &["known-networks", "list"], WIFI_SCAN_TIMEOUT),
+    );
+
+    // Rows look like "  >  Name with spaces   psk   ****": the security and
+    // signal columns are single words, the name is whatever precedes them.
+    const SECURITY: [&str; 4] = ["open", "psk", "8021x", "wep"];
+    let known: Vec<String> = iwctl_rows(&known.unwrap_or_default())
+        .iter()
+        .filter_map(|row| {
+            let words: Vec<&str> = row.split_whitespace().collect();
+            let sec = words.iter().position(|w| SECURITY.contains(w))?;
+            Some(words[..sec].join(" "))
+        })
+        .collect();
+
+    let nets = iwctl_rows(&list?)
+        .iter()
+        .filter_map(|row| {
+            let row    = row.trim();
+            let active = row.starts_with('>');
+            let mut words: Vec<&str> = row.trim_start_matches('>').split_whitespace().collect();
+            let stars    = words.pop()?;
+            let security = words.pop()?;
+            let ssid     = words.join(" ");
+            (!ssid.is_empty()).then(|| WifiNetwork {
+                known:   known.contains(&ssid),
+                ssid,
+                signal:  (stars.matches('*').count() * 25).min(100) as u8,
+                secured: security != "open",
+                active,
+            })
+        })
+        .collect();
+    Ok(nets)
+}
+
+async fn connect_wifi(backend: String, ssid: String) -> Result<String, String> {
+    if backend == "iwd" {
+        let dev = wireless_iface().ok_or("no wireless interface found")?;
+        run_with_timeout(
+            "iwctl",
+            &["--dont-ask", "station", &dev, "connect", &ssid],
+            WIFI_CONNECT_TIMEOUT,
+        ).await?;
+    } else {
+        run_with_timeout("nmcli", &["device", "wifi", "connect", &ssid], WIFI_CONNECT_TIMEOUT).await?;
+    }
+    Ok(format!("Connected to {ssid}"))
+}
+
 /// Query bluetooth via bluetoothctl.
 async fn read_bluetooth() -> (bool, Option<String>) {
     let out = tokio::process::Command::new("bluetoothctl")
@@ -358,6 +1149,77 @@ async fn read_bluetooth() -> (bool, Option<String>) {
     (false, None)
 }
 
+/// Quiet period after a UPower event before re-reading the devices, so a
+/// burst of property changes costs one `upower --dump`.
+const PERIPHERAL_SETTLE: Duration = Duration::from_millis(500);
+
+/// Battery-powered peripherals via `upower --dump`, lowest charge first.
+async fn read_peripherals() -> Vec<Peripheral> {
+    match run_with_timeout("upower", &["--dump"], Duration::from_secs(3)).await {
+        Ok(raw) => parse_upower_dump(&raw),
+        Err(e)  => {
+            tracing::debug!("upower: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Follow UPower's DeviceAdded, DeviceRemoved and PropertiesChanged signals
+/// through `upower --monitor-detail` until it exits, sending the device
+/// list at the start and after every peripheral event.
+async fn watch_peripherals(sender: &mut Sender<Message>, retry: &mut Backoff) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let mut child = tokio::process::Command::new("upower")
+        .arg("--monitor-detail")
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let Some(stdout) = child.stdout.take() else { return Ok(()) };
+    let mut lines = BufReader::new(stdout).lines();
+
+    let mut found = read_peripherals().await;
+    loop {
+        if sender.send(Message::SliceReady(Slice::Peripherals(found))).await.is_err() {
+            return Ok(());
+        }
+        // Wait for a peripheral to come, go or change
+        loop {
+            let Some(line) = lines.next_line().await? else { return Ok(()) };
+            if peripheral_event(&line) {
+                break;
+            }
+        }
+        retry.reset();
+        while let Ok(Ok(Some(_))) = tokio::time::timeout(PERIPHERAL_SETTLE, lines.next_line()).await {}
+        found = read_peripherals().await;
+    }
+}
+
+/// Pending package updates from `checkupdates`, or `None` when it isn't
+/// installed or doesn't finish within UPDATES_TIMEOUT (it syncs a package
+/// database first, which can stall on a slow mirror).
+async fn read_updates() -> Option<u32> {
+    let run = tokio::process::Command::new("checkupdates").kill_on_drop(true).output();
+    match tokio::time::timeout(UPDATES_TIMEOUT, run).await {
+        // Exit status 2 means "no updates", so only the output counts.
+        Ok(Ok(out)) => Some(
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .count() as u32,
+        ),
+        Ok(Err(e)) => {
+            tracing::debug!("checkupdates: {e}");
+            None
+        }
+        Err(_) => {
+            tracing::warn!("checkupdates took over {}s; keeping the last count", UPDATES_TIMEOUT.as_secs());
+            None
+        }
+    }
+}
+
 /// Fetch weather from wttr.in using curl — no new dependency.
 async fn read_weather(location: String) -> String {
     if location.is_empty() {
@@ -504,56 +1366,693 @@ impl canvas::Program<Message> for NoiseOverlay {
 #[derive(Debug, Clone)]
 enum Message {
     SysReady(DashSnapshot),
+    /// A slow source's reading from `slow_stream`.
+    SliceReady(Slice),
     Dismiss,
     VolumeSet(f32),
     BrightnessSet(u8),
+    /// Mouse wheel or touchpad scroll over the `"volume"` or `"brightness"` card.
+    CardScroll(&'static str, iced::mouse::ScrollDelta),
     MediaAction(&'static str),
+    /// Switch the media card to the next running player.
+    MediaCyclePlayer,
+    /// Open or close the Wi-Fi picker (click on the network card).
+    WifiToggle,
+    WifiScanned(Result<Vec<WifiNetwork>, String>),
+    WifiConnect(String),
+    WifiConnected(Result<String, String>),
     PowerAction(&'static str),
+    /// Open or close the drawer group card at this position in `dashboard.items`.
+    DrawerToggle(usize),
+    /// Show a notice under the grid, e.g. the metrics endpoint couldn't bind.
+    Notice(String),
+    /// Close the notice.
+    NoticeDismiss,
+    /// Close the first-run hint.
+    HintDismiss,
+    /// Start the caffeine timer with the selected duration, or cancel it.
+    CaffeineToggle,
+    /// Select the next caffeine duration (right-click); restarts a running timer.
+    CaffeineCycle,
+    /// Run the command palette entry at this position in the filtered list.
+    PaletteRun(usize),
+    /// Launch `bar-editor` from the first-run hint and close the dashboard.
+    OpenEditor,
+    /// Run a Hyprland dispatcher over the command socket, e.g. `"workspace 2"`.
+    HyprDispatch(String),
+    /// All outputs were powered off (`false`) or one woke up again (`true`).
+    OutputsPower(bool),
+    /// The config file (or one of its includes) changed and was re-read.
+    ConfigReloaded(Box<DashConfig>),
+    /// The desktop's reduced-motion preference, for `reduce_motion = "auto"`.
+    ReducedMotion(bool),
     AnimFrame,
     KeyEvent(iced::keyboard::Event),
 }
 
-// ── State ─────────────────────────────────────────────────────────────────────
+impl Message {
+    /// Variant name, for the debug card's message counters.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::SysReady(_)         => "SysReady",
+            Self::SliceReady(_)       => "SliceReady",
+            Self::Dismiss             => "Dismiss",
+            Self::VolumeSet(_)        => "VolumeSet",
+            Self::BrightnessSet(_)    => "BrightnessSet",
+            Self::CardScroll(..)      => "CardScroll",
+            Self::MediaAction(_)      => "MediaAction",
+            Self::MediaCyclePlayer    => "MediaCyclePlayer",
+            Self::WifiToggle          => "WifiToggle",
+            Self::WifiScanned(_)      => "WifiScanned",
+            Self::WifiConnect(_)      => "WifiConnect",
+            Self::WifiConnected(_)    => "WifiConnected",
+            Self::PowerAction(_)      => "PowerAction",
+            Self::DrawerToggle(_)     => "DrawerToggle",
+            Self::OutputsPower(_)     => "OutputsPower",
+            Self::HyprDispatch(_)     => "HyprDispatch",
+            Self::Notice(_)           => "Notice",
+            Self::NoticeDismiss       => "NoticeDismiss",
+            Self::CaffeineToggle      => "CaffeineToggle",
+            Self::CaffeineCycle       => "CaffeineCycle",
+            Self::PaletteRun(_)       => "PaletteRun",
+            Self::HintDismiss         => "HintDismiss",
+            Self::OpenEditor          => "OpenEditor",
+            Self::ConfigReloaded(_)   => "ConfigReloaded",
+            Self::ReducedMotion(_)    => "ReducedMotion",
+            Self::AnimFrame           => "AnimFrame",
+            Self::KeyEvent(_)         => "KeyEvent",
+            _                         => "LayerShell",
+        }
+    }
+}
 
-struct Dashboard {
-    theme:            Theme,
-    dash_config:      DashboardConfig,
-    lock_command:     String,
-    weather_location: String,
-    sys:              DashSnapshot,
-    eq_tick:          u64,
-    /// Entrance animation progress: 0.0 (hidden) → 1.0 (fully revealed).
-    /// Incremented each AnimFrame tick by DT_PER_FRAME, reaching 1.0 in ~300ms.
-    intro_t:          f32,
+// ── Command palette ───────────────────────────────────────────────────────────
+
+/// Search state of the open command palette.
+#[derive(Debug, Clone, Default)]
+struct Palette {
+    query:    String,
+    /// Highlighted row in the filtered list.
+    selected: usize,
 }
 
-/// Per-tick increment so intro_t reaches 1.0 in ~18 ticks (~300ms at 60fps).
-const INTRO_DT: f32 = 1.0 / 18.0;
+/// Most rows the palette shows at once.
+const PALETTE_ROWS: usize = 8;
 
-impl Dashboard {
-    fn new() -> (Self, Task<Message>) {
-        let config           = load_config(default_path()).unwrap_or_default();
-        let theme            = Theme::from_config(&config.theme);
-        let dash_config      = config.dashboard.clone();
-        let lock_command     = config.lock_command.clone();
-        let weather_location = config.weather_location.clone();
+#[derive(Debug, Clone)]
+struct PaletteItem {
+    label:  String,
+    action: PaletteAction,
+}
 
-        let loc = weather_location.clone();
-        let dash = Self {
-            theme, dash_config, lock_command, weather_location,
-            sys: DashSnapshot::default(),
-            eq_tick: 0,
-            intro_t: 0.0,
+#[derive(Debug, Clone)]
+enum PaletteAction {
+    /// Handle a message as if its control had been clicked.
+    Send(Box<Message>),
+    /// Run a Hyprland dispatcher, then close.
+    Dispatch(String),
+    /// Run a `[[palette]]` shell command, then close.
+    Shell(String),
+}
+
+/// Built-in palette actions, in the order shown for an empty search.
+fn builtin_palette_items() -> Vec<PaletteItem> {
+    let send = |label: &str, msg: Message| PaletteItem { label: label.to_string(), action: PaletteAction::Send(Box::new(msg)) };
+    let mut items = vec![
+        send("Play / pause",        Message::MediaAction("play-pause")),
+        send("Next track",          Message::MediaAction("next")),
+        send("Previous track",      Message::MediaAction("previous")),
+        send("Switch media player", Message::MediaCyclePlayer),
+        send("Wi-Fi networks",      Message::WifiToggle),
+        send("Caffeine on / off",   Message::CaffeineToggle),
+        send("Open editor",         Message::OpenEditor),
+        send("Lock",                Message::PowerAction("lock")),
+        send("Sleep",               Message::PowerAction("sleep")),
+        send("Hibernate",           Message::PowerAction("hibernate")),
+        send("Log out",             Message::PowerAction("logout")),
+        send("Reboot",              Message::PowerAction("reboot")),
+        send("Shut down",           Message::PowerAction("shutdown")),
+    ];
+    items.extend((1..=10).map(|n| PaletteItem {
+        label:  format!("Workspace {n}"),
+        action: PaletteAction::Dispatch(format!("workspace {n}")),
+    }));
+    items
+}
+
+/// Skim-style subsequence score of `query` against `label`, ignoring case
+/// and spaces in the query.  `None` unless every query character appears in
+/// order.  Matches at the start of a word or right after the previous match
+/// score higher; skipped characters cost a point each.
+fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let label: Vec<char> = label.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut from  = 0;
+    let mut prev: Option<usize> = None;
+    for q in query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase) {
+        let at = (from..label.len()).find(|&i| label[i] == q)?;
+        score += 1;
+        if prev.is_some_and(|p| p + 1 == at) {
+            score += 5;
+        }
+        if at == 0 || !label[at - 1].is_alphanumeric() {
+            score += 8;
+        }
+        score -= (at - from) as i32;
+        prev = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
+
+// ── Scrolling ─────────────────────────────────────────────────────────────────
+
+/// Pixels of touchpad scrolling that count as one wheel notch.
+const PIXELS_PER_STEP: f32 = 40.0;
+
+/// Wheel notches closer together than this count as one fast scroll.
+const SCROLL_ACCEL_WINDOW: Duration = Duration::from_millis(100);
+
+/// Turns scroll events into whole slider steps.  Touchpads send many small
+/// pixel deltas; these add up and a step is taken only once a full one has
+/// built up, with the remainder kept for the next event.
+#[derive(Debug, Default)]
+struct ScrollAccumulator {
+    /// Fractional steps not applied yet.
+    pending: f32,
+    last:    Option<Instant>,
+    /// Consecutive fast events, for acceleration.
+    streak:  u32,
+}
+
+impl ScrollAccumulator {
+    /// Feed one scroll event and return the whole steps to move, positive
+    /// for up.  With `accelerate`, fast wheel notches move 1.5×, 2×… up to
+    /// 4× the step.
+    fn feed(&mut self, delta: iced::mouse::ScrollDelta, accelerate: bool) -> i32 {
+        use iced::mouse::ScrollDelta;
+
+        let now  = Instant::now();
+        let fast = self.last.is_some_and(|at| now.duration_since(at) < SCROLL_ACCEL_WINDOW);
+        self.last = Some(now);
+        // A new gesture starts from scratch rather than finishing an old one.
+        if fast {
+            self.streak += 1;
+        } else {
+            self.pending = 0.0;
+            self.streak  = 0;
+        }
+
+        let steps = match delta {
+            ScrollDelta::Lines { y, .. } => {
+                let factor = if accelerate { (1.0 + self.streak as f32 * 0.5).min(4.0) } else { 1.0 };
+                y * factor
+            }
+            // Touchpads already scale deltas with finger speed.
+            ScrollDelta::Pixels { y, .. } => y / PIXELS_PER_STEP,
         };
-        let task = Task::perform(
-            async move { read_sys_snapshot(loc).await },
-            Message::SysReady,
+        self.pending += steps;
+        let whole = self.pending.trunc();
+        self.pending -= whole;
+        whole as i32
+    }
+}
+
+// ── Caffeine ──────────────────────────────────────────────────────────────────
+
+/// A timed idle inhibitor.  The `systemd-inhibit` process outlives the
+/// dashboard, so its deadline and pid go to a runtime file where the next
+/// launch picks the countdown back up.
+#[derive(Debug, Clone, Copy)]
+struct Caffeine {
+    until: Instant,
+    pid:   u32,
+}
+
+/// `$XDG_RUNTIME_DIR/bar-caffeine`, holding `<deadline unix secs> <pid>`.
+fn caffeine_state_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|d| !d.is_empty())
+        .map(|d| PathBuf::from(d).join("bar-caffeine"))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// The inhibitor started by an earlier launch, unless it has expired.
+fn load_caffeine() -> Option<Caffeine> {
+    let raw = std::fs::read_to_string(caffeine_state_path()?).ok()?;
+    let mut parts = raw.split_whitespace();
+    let deadline: u64 = parts.next()?.parse().ok()?;
+    let pid:      u32 = parts.next()?.parse().ok()?;
+    let left = deadline.checked_sub(unix_now()).filter(|&s| s > 0)?;
+    Some(Caffeine { until: Instant::now() + Duration::from_secs(left), pid })
+}
+
+/// Inhibit idle for `minutes`.  `None` (logged) if `systemd-inhibit` can't run.
+fn start_caffeine(minutes: u32) -> Option<Caffeine> {
+    let secs = u64::from(minutes) * 60;
+    let mut child = std::process::Command::new("systemd-inhibit")
+        .args(["--what=idle", "--who=bar-dashboard", "--why=Caffeine", "sleep", &secs.to_string()])
+        .spawn()
+        .map_err(|e| tracing::warn!("Cannot start systemd-inhibit: {e}"))
+        .ok()?;
+    let pid = child.id();
+    // Reap it if it finishes while the dashboard is still open
+    std::thread::spawn(move || child.wait());
+
+    if let Some(path) = caffeine_state_path() {
+        if let Err(e) = std::fs::write(&path, format!("{} {pid}\n", unix_now() + secs)) {
+            tracing::warn!("Cannot write '{}': {e}", path.display());
+        }
+    }
+    Some(Caffeine { until: Instant::now() + Duration::from_secs(secs), pid })
+}
+
+/// Release the inhibitor (if it's still running) and forget it.
+fn stop_caffeine(caffeine: Caffeine) {
+    // Once expired the pid may already belong to something else
+    if caffeine.until > Instant::now() {
+        let _ = std::process::Command::new("kill").arg(caffeine.pid.to_string()).status();
+    }
+    if let Some(path) = caffeine_state_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+// ── Metrics ───────────────────────────────────────────────────────────────────
+
+/// Cheap runtime counters, always collected and shown by the `debug` card.
+#[derive(Debug, Default)]
+struct Metrics {
+    /// Messages handled so far, per variant.
+    messages:      Vec<(&'static str, u64)>,
+    /// How long the last snapshot read took.
+    read_time:     Duration,
+    /// Time from the last snapshot being taken to it reaching `update`.
+    delivery:      Duration,
+    /// Liveness of the two periodic streams.
+    last_snapshot: Option<Instant>,
+    last_frame:    Option<Instant>,
+}
+
+impl Metrics {
+    fn count(&mut self, name: &'static str) {
+        match self.messages.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => *count += 1,
+            None => self.messages.push((name, 1)),
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.messages.iter().map(|(_, c)| c).sum()
+    }
+
+    /// Age of the stalest periodic stream, with its name.
+    fn stalest(&self) -> Option<(&'static str, Duration)> {
+        [("sys", self.last_snapshot), ("anim", self.last_frame)]
+            .into_iter()
+            .filter_map(|(name, at)| Some((name, at?.elapsed())))
+            .max_by_key(|(_, age)| *age)
+    }
+}
+
+// ── State ─────────────────────────────────────────────────────────────────────
+
+struct Dashboard {
+    theme:            Theme,
+    dash_config:      DashboardConfig,
+    lock_command:     String,
+    weather_location: String,
+    /// Options the background snapshot stream runs with; changing them
+    /// restarts the stream.
+    read_opts:        ReadOptions,
+    /// `media_player` from the config, to tell whether a reload changed it.
+    media_player:     String,
+    /// Hide the media card once playback has been stopped this long.
+    media_hide_paused_after: Option<Duration>,
+    /// The peripherals card lists only devices at or below this charge.
+    peripherals_only_below: Option<u8>,
+    sys:              DashSnapshot,
+    eq_tick:          u64,
+    /// Entrance animation progress: 0.0 (hidden) → 1.0 (fully revealed).
+    /// Incremented each AnimFrame tick by DT_PER_FRAME, reaching 1.0 in ~300ms.
+    intro_t:          f32,
+    /// Previous `(cpu_ticks, sampled_at)` pair used to derive `self_cpu_pct`.
+    self_sample:      Option<(u64, Instant)>,
+    /// `user@host` identity shown by the host card.
+    host_info:        HostInfo,
+    /// Set once the stale-snapshot error has been logged, cleared on recovery.
+    stale_logged:     bool,
+    /// Message counters and stream liveness for the debug card.
+    metrics:          Metrics,
+    /// `"networkmanager"` or `"iwd"`.
+    wifi_backend:     String,
+    wifi_open:        bool,
+    /// A scan or connection attempt is in flight.
+    wifi_busy:        bool,
+    wifi_networks:    Vec<WifiNetwork>,
+    wifi_scanned_at:  Option<Instant>,
+    /// Result of the last scan failure or connection attempt.
+    wifi_status:      Option<Result<String, String>>,
+    /// Drawer group cards by position in `dashboard.items`; absent means closed.
+    drawers:          HashMap<usize, Drawer>,
+    /// Every output is DPMS-off: nothing is animated and snapshots are sparse.
+    outputs_asleep:   bool,
+    /// Partial scroll steps on the volume and brightness cards.
+    volume_scroll:     ScrollAccumulator,
+    brightness_scroll: ScrollAccumulator,
+    /// Running caffeine timer, if any.
+    caffeine:         Option<Caffeine>,
+    /// Caffeine durations in minutes, from `caffeine_minutes`.
+    caffeine_minutes: Vec<u32>,
+    /// Index into `caffeine_minutes` that the next click starts.
+    caffeine_preset:  usize,
+    /// Where to serve `/metrics`, from `metrics_listen`; `None` = disabled.
+    metrics_listen:   Option<SocketAddr>,
+    /// Problem reported by a background task, shown until dismissed.
+    notice:           Option<String>,
+    /// Open command palette, shown in place of the grid.
+    palette:          Option<Palette>,
+    /// `[[palette]]` entries from the config.
+    palette_entries:  Vec<PaletteEntry>,
+    /// Show where the freshly written default config is.  Only ever set on
+    /// the run that created the file, so it never comes back.
+    first_run_hint:   bool,
+}
+
+/// Open state and slide progress of a `drawer = true` group card.
+#[derive(Debug, Clone, Copy, Default)]
+struct Drawer {
+    open: bool,
+    /// 0.0 (only the first child shown) → 1.0 (fully slid out).
+    t:    f32,
+}
+
+impl Drawer {
+    fn animating(&self) -> bool {
+        self.t != if self.open { 1.0 } else { 0.0 }
+    }
+}
+
+/// Per-tick increment so intro_t reaches 1.0 in ~18 ticks (~300ms at 60fps).
+const INTRO_DT: f32 = 1.0 / 18.0;
+
+/// Per-tick drawer slide step (~200ms at 60fps).
+const DRAWER_DT: f32 = 1.0 / 12.0;
+
+/// Space either side of the separator between a group's children.
+const GROUP_SEP_PAD: f32 = 10.0;
+
+/// How often `sys_stream` takes a new snapshot.
+const SYS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Snapshot interval while every output is powered off.  Output power itself
+/// is still checked every `SYS_POLL_INTERVAL`, so waking up is noticed quickly.
+const ASLEEP_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often output power is checked while an output is on.  Going to sleep
+/// can be noticed late; waking up is checked on every poll.
+const DPMS_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Longest gap between output power checks while Hyprland can't be asked
+/// (not running under it, socket errors); see [`Backoff`].
+const DPMS_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Quiet period after a config file event before reloading.
+const CONFIG_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Snapshots older than this are treated as stale and their cards dimmed.
+const STALE_AFTER: Duration = Duration::from_secs(SYS_POLL_INTERVAL.as_secs() * 3);
+
+/// Longest `checkupdates` may run before its result is dropped.
+const UPDATES_TIMEOUT: Duration = Duration::from_secs(120);
+
+impl Dashboard {
+    fn new() -> (Self, Task<Message>) {
+        let config = startup_config();
+        if !config.media_player.is_empty() {
+            select_player(Some(config.media_player.clone()));
+        }
+
+        let dash = Self::with_config(config);
+        let hooks = run_hooks("on_start", &config.on_start);
+        // Asked once, off the UI thread; reloads reuse the cached answer.
+        let motion = Task::perform(
+            async {
+                tokio::task::spawn_blocking(bar_theme::detect_reduced_motion)
+                    .await
+                    .unwrap_or(false)
+            },
+            Message::ReducedMotion,
         );
-        (dash, task)
+        tracing::info!("startup: dashboard state ready after {:?}", since_start());
+        (dash, Task::batch([hooks, motion]))
+    }
+
+    /// Fresh state for `config`, before any snapshot has been read.
+    fn with_config(config: &DashConfig) -> Self {
+        let theme            = Theme::from_config(&config.theme);
+        let dash_config      = config.dashboard.clone();
+        let lock_command     = config.lock_command.clone();
+        let weather_location = config.weather_location.clone();
+        let intro_t          = if theme.animate() { 0.0 } else { 1.0 };
+        let read_opts        = ReadOptions::from_config(config);
+        Self {
+            theme, dash_config, lock_command, weather_location,
+            read_opts,
+            media_player: config.media_player.clone(),
+            media_hide_paused_after: config.media_hide_paused_after.map(Duration::from_secs),
+            peripherals_only_below: config.peripherals_only_below,
+            sys: DashSnapshot::default(),
+            eq_tick: 0,
+            intro_t,
+            self_sample: None,
+            host_info: HostInfo::detect(),
+            stale_logged: false,
+            metrics: Metrics::default(),
+            wifi_backend: config.wifi_backend.clone(),
+            wifi_open: false,
+            wifi_busy: false,
+            wifi_networks: Vec::new(),
+            wifi_scanned_at: None,
+            wifi_status: None,
+            drawers: HashMap::new(),
+            outputs_asleep: false,
+            volume_scroll: ScrollAccumulator::default(),
+            brightness_scroll: ScrollAccumulator::default(),
+            caffeine: load_caffeine(),
+            caffeine_minutes: config.caffeine_minutes.clone(),
+            caffeine_preset: 0,
+            metrics_listen: parse_listen(&config.metrics_listen),
+            palette: std::env::args().any(|a| a == "--palette").then(Palette::default),
+            palette_entries: config.palette.clone(),
+            first_run_hint: FIRST_RUN.load(Ordering::Relaxed),
+            notice: None,
+        }
     }
 
+    /// `dashboard.namespace` from the startup config; a reload can't rename
+    /// a surface that already exists.
     fn namespace() -> String {
-        "bar-dashboard".to_string()
+        startup_config().dashboard.layer_namespace().to_string()
+    }
+
+    /// Swap in a reloaded config.  Only config-derived fields change: the
+    /// snapshot and its history, animation progress, open drawers, the
+    /// caffeine duration and the player picked on the media card all carry
+    /// over.  The font is fixed at startup.
+    fn apply_config(&mut self, config: DashConfig) {
+        let old_items   = std::mem::replace(&mut self.dash_config, config.dashboard.clone()).items;
+        let old_minutes = std::mem::take(&mut self.caffeine_minutes);
+        self.theme            = Theme::from_config(&config.theme);
+        self.lock_command     = config.lock_command.clone();
+        self.weather_location = config.weather_location.clone();
+        self.read_opts        = ReadOptions::from_config(&config);
+        self.wifi_backend     = config.wifi_backend.clone();
+        self.metrics_listen   = parse_listen(&config.metrics_listen);
+        self.caffeine_minutes = config.caffeine_minutes.clone();
+        self.media_hide_paused_after = config.media_hide_paused_after.map(Duration::from_secs);
+        self.peripherals_only_below  = config.peripherals_only_below;
+        self.palette_entries  = config.palette.clone();
+        self.caffeine_preset  = carry_preset(&old_minutes, &self.caffeine_minutes, self.caffeine_preset);
+        self.drawers = carry_drawers(&old_items, &self.dash_config.items, std::mem::take(&mut self.drawers));
+
+        // Re-pin only when the configured player itself changed, so a reload
+        // doesn't undo a player the user switched to on the card.
+        if config.media_player != self.media_player {
+            let pin = (!config.media_player.is_empty()).then(|| config.media_player.clone());
+            select_player(pin);
+            self.media_player = config.media_player;
+        }
+    }
+
+    /// Palette entries matching the current search, best first.  Built-ins
+    /// come before `[[palette]]` entries when scores tie.
+    fn palette_matches(&self) -> Vec<PaletteItem> {
+        let query = self.palette.as_ref().map_or("", |p| p.query.as_str());
+        let user = self.palette_entries.iter().map(|e| PaletteItem {
+            label:  e.label.clone(),
+            action: PaletteAction::Shell(e.command.clone()),
+        });
+        let mut scored: Vec<(i32, PaletteItem)> = builtin_palette_items()
+            .into_iter()
+            .chain(user)
+            .filter_map(|item| Some((fuzzy_score(query, &item.label)?, item)))
+            .collect();
+        if !query.is_empty() {
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        }
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// A key press while the palette is open: type to search, arrows to
+    /// move, Enter to run, Escape to close (back to the grid).
+    fn palette_key(&mut self, key: iced::keyboard::Key<&str>, typed: Option<&str>) -> Task<Message> {
+        use iced::keyboard::{key::Named, Key};
+        let shown = self.palette_matches().len().min(PALETTE_ROWS);
+        let Some(palette) = self.palette.as_mut() else { return Task::none() };
+        match key {
+            Key::Named(Named::Escape)    => self.palette = None,
+            Key::Named(Named::Enter)     => return Task::done(Message::PaletteRun(palette.selected)),
+            Key::Named(Named::ArrowDown) => palette.selected = (palette.selected + 1).min(shown.saturating_sub(1)),
+            Key::Named(Named::ArrowUp)   => palette.selected = palette.selected.saturating_sub(1),
+            Key::Named(Named::Backspace) => {
+                palette.query.pop();
+                palette.selected = 0;
+            }
+            _ => {
+                if let Some(typed) = typed.filter(|s| !s.chars().any(char::is_control)) {
+                    palette.query.push_str(typed);
+                    palette.selected = 0;
+                }
+            }
+        }
+        Task::none()
+    }
+
+    /// Minutes the next caffeine click inhibits idle for.
+    fn caffeine_duration(&self) -> u32 {
+        self.caffeine_minutes.get(self.caffeine_preset).copied().unwrap_or(30)
+    }
+
+    /// `dashboard.direction = "rtl"`: rows and groups run right to left.
+    fn rtl(&self) -> bool {
+        self.dash_config.direction.eq_ignore_ascii_case("rtl")
+    }
+
+    /// The configured `byte_units`.
+    fn byte_units(&self) -> ByteUnits {
+        ByteUnits::from_config(&self.theme.byte_units)
+    }
+
+    /// Format a byte size in the configured `byte_units`.
+    fn fmt_size(&self, bytes: u64) -> String {
+        self.fmt_num(format_bytes(bytes, self.byte_units()))
+    }
+
+    /// Apply the configured `decimal_separator` to formatted numbers.
+    fn fmt_num(&self, s: String) -> String {
+        localize_decimal(s, self.theme.decimal_separator)
+    }
+
+    /// Format a per-second network rate in the configured `rate_units`.
+    fn fmt_rate(&self, bytes_per_sec: u64) -> String {
+        if self.theme.rate_units == "bits" {
+            self.fmt_num(format_bits(bytes_per_sec))
+        } else {
+            self.fmt_size(bytes_per_sec)
+        }
+    }
+
+    /// The snapshot and the dashboard's own counters in the Prometheus text
+    /// exposition format.
+    fn prometheus_text(&self) -> String {
+        use std::fmt::Write;
+
+        let s    = &self.sys;
+        let host = format!("host=\"{}\"", prom_escape(&self.host_info.host));
+        let mut out = String::new();
+
+        let mut gauge = |name: &str, help: &str, labels: &str, value: f64| {
+            let _ = writeln!(out, "# HELP bar_{name} {help}");
+            let _ = writeln!(out, "# TYPE bar_{name} gauge");
+            let _ = writeln!(out, "bar_{name}{{{labels}}} {value}");
+        };
+        gauge("cpu_usage_percent", "CPU usage across all cores.", &host, s.cpu_pct as f64);
+        gauge("memory_used_bytes", "Memory in use.", &host, s.ram_used as f64);
+        gauge("memory_total_bytes", "Installed memory.", &host, s.ram_total as f64);
+        gauge("swap_used_bytes", "Swap in use.", &host, s.swap_used as f64);
+        gauge("swap_total_bytes", "Swap size.", &host, s.swap_total as f64);
+        gauge("disk_used_bytes", "Space used on the root filesystem.", &host, s.disk_used as f64);
+        gauge("disk_total_bytes", "Size of the root filesystem.", &host, s.disk_total as f64);
+        gauge("load1", "1-minute load average.", &host, s.load_1 as f64);
+        if let Some((name, _)) = s.network() {
+            let iface = format!("{host},interface=\"{}\"", prom_escape(name));
+            gauge("network_receive_bytes_per_second", "Download rate.", &iface, s.net_rx_bps as f64);
+            gauge("network_transmit_bytes_per_second", "Upload rate.", &iface, s.net_tx_bps as f64);
+        }
+        if let Some((pct, _)) = s.battery() {
+            gauge("battery_percent", "Battery charge.", &host, pct as f64);
+        }
+        if let Some(temp) = s.temp_celsius {
+            gauge("temperature_celsius", "CPU temperature.", &host, temp as f64);
+        }
+        gauge(
+            "snapshot_read_seconds",
+            "How long the last snapshot read took.",
+            &host,
+            s.read_time.as_secs_f64(),
+        );
+
+        let _ = writeln!(out, "# HELP bar_messages_total Messages handled by the dashboard.");
+        let _ = writeln!(out, "# TYPE bar_messages_total counter");
+        for (name, count) in &self.metrics.messages {
+            let _ = writeln!(out, "bar_messages_total{{{host},message=\"{name}\"}} {count}");
+        }
+        out
+    }
+
+    /// Value for a `{placeholder}` in a metrics card template.
+    fn metric_value(&self, name: &str) -> Option<String> {
+        let s   = &self.sys;
+        let pad = self.theme.pad_numbers;
+        let or_dash = |v: Option<String>| v.unwrap_or_else(|| "--".to_string());
+        Some(match name {
+            "cpu"        => fmt_pct(s.cpu_pct, pad),
+            "mem"        => self.fmt_size(s.ram_used),
+            "mem_total"  => self.fmt_size(s.ram_total),
+            "ram_pct"    => fmt_pct(s.ram_frac() * 100.0, pad),
+            "swap"       => self.fmt_size(s.swap_used),
+            "swap_pct"   => fmt_pct(s.swap_frac() * 100.0, pad),
+            "disk"       => self.fmt_size(s.disk_used),
+            "disk_pct"   => fmt_pct(s.disk_frac() * 100.0, pad),
+            "temp"       => or_dash(s.temp_celsius.map(|c| format!("{:.0}", degrees(c, &self.theme.temperature_unit)))),
+            "net_rx"     => self.fmt_rate(s.net_rx_bps),
+            "net_tx"     => self.fmt_rate(s.net_tx_bps),
+            "iface"      => s.net_iface.clone(),
+            "signal"     => or_dash(s.net_signal_pct.map(|p| fmt_pct(p as f32, pad))),
+            "signal_dbm" => or_dash(s.net_signal_dbm.map(|d| d.to_string())),
+            "load1"      => self.fmt_num(format!("{:.2}", s.load_1)),
+            "load5"      => self.fmt_num(format!("{:.2}", s.load_5)),
+            "load15"     => self.fmt_num(format!("{:.2}", s.load_15)),
+            "uptime"     => fmt_uptime(s.uptime_secs),
+            "battery"    => or_dash(s.battery().map(|(p, _)| fmt_pct(p as f32, pad))),
+            "volume"     => or_dash(s.volume.map(|v| fmt_pct(v * 100.0, pad))),
+            "brightness" => or_dash(s.brightness.map(|b| fmt_pct(b as f32, pad))),
+            "gpu"        => or_dash(s.gpu_percent.map(|p| fmt_pct(p, pad))),
+            "updates"    => or_dash(s.update_count.map(|n| n.to_string())),
+            "host"       => self.host_info.host.clone(),
+            "user"       => self.host_info.user.clone(),
+            _ => return None,
+        })
+    }
+
+    /// True when the last snapshot is older than [`STALE_AFTER`].
+    fn is_stale(&self) -> bool {
+        // Reads are deliberately sparse while the screens are off.
+        !self.outputs_asleep && self.sys.sampled_at.is_some_and(|at| at.elapsed() > STALE_AFTER)
     }
 
     /// Merge a fresh snapshot into `self.sys` — preserving the rolling history buffers.
@@ -573,50 +2072,283 @@ impl Dashboard {
         snap.cpu_history    = cpu_hist;
         snap.net_rx_history = rx_hist;
 
+        // Slow sources arrive on their own (apply_slice) and carry over
+        snap.disk_used    = self.sys.disk_used;
+        snap.disk_total   = self.sys.disk_total;
+        snap.update_count = self.sys.update_count;
+        snap.weather_text = std::mem::take(&mut self.sys.weather_text);
+        snap.peripherals  = std::mem::take(&mut self.sys.peripherals);
+        // So do audio and media, which aren't read while the outputs are off
+        if snap.asleep {
+            snap.volume        = self.sys.volume;
+            snap.volume_muted  = self.sys.volume_muted;
+            snap.audio_port    = self.sys.audio_port.take();
+            snap.media_title   = self.sys.media_title.take();
+            snap.media_artist  = self.sys.media_artist.take();
+            snap.media_playing = self.sys.media_playing;
+            snap.media_players = std::mem::take(&mut self.sys.media_players);
+        }
+        // A failed battery read keeps the last good value so the card doesn't
+        // flicker out on a laptop.
+        if snap.battery_failed {
+            snap.battery_pct      = self.sys.battery_pct;
+            snap.battery_charging = self.sys.battery_charging;
+        }
+
+        // Own CPU% = ticks consumed since the last sample / elapsed wall time.
+        // Linux reports ticks in USER_HZ, which is 100 on every mainstream arch.
+        const TICKS_PER_SEC: f32 = 100.0;
+        let now = Instant::now();
+        if let Some((prev_ticks, prev_at)) = self.self_sample {
+            let elapsed = now.duration_since(prev_at).as_secs_f32();
+            if elapsed > 0.0 {
+                let used = snap.self_cpu_ticks.saturating_sub(prev_ticks) as f32 / TICKS_PER_SEC;
+                snap.self_cpu_pct = used / elapsed * 100.0;
+            }
+        }
+        self.self_sample = Some((snap.self_cpu_ticks, now));
+
+        snap.media_state_since = match self.sys.media_state_since {
+            Some(since) if snap.media_playing == self.sys.media_playing => Some(since),
+            _ => Some(now),
+        };
+
         self.sys = snap;
     }
 
+    /// Store a slow source's reading in the current snapshot.
+    fn apply_slice(&mut self, slice: Slice) {
+        match slice {
+            Slice::Disk { used, total } => {
+                self.sys.disk_used  = used;
+                self.sys.disk_total = total;
+            }
+            Slice::Updates(count)     => self.sys.update_count = Some(count),
+            Slice::Weather(text)      => self.sys.weather_text = text,
+            Slice::Peripherals(found) => self.sys.peripherals  = found,
+        }
+    }
+
     fn update(&mut self, msg: Message) -> Task<Message> {
+        self.metrics.count(msg.name());
         match msg {
-            Message::SysReady(snap) => { self.merge_snapshot(snap); }
+            Message::SysReady(snap) => {
+                if self.sys.sampled_at.is_none() {
+                    tracing::info!("startup: first snapshot after {:?}", since_start());
+                }
+                self.metrics.last_snapshot = Some(Instant::now());
+                self.metrics.read_time     = snap.read_time;
+                self.metrics.delivery      = snap.sampled_at.map(|at| at.elapsed()).unwrap_or_default();
+                self.merge_snapshot(snap);
+                if self.metrics_listen.is_some() {
+                    exported_metrics().send_replace(self.prometheus_text());
+                }
+            }
+            Message::SliceReady(slice) => self.apply_slice(slice),
             Message::Dismiss => std::process::exit(0),
-            Message::KeyEvent(iced::keyboard::Event::KeyPressed { key, .. }) => {
-                if key == iced::keyboard::Key::Named(iced::keyboard::key::Named::Escape) {
-                    std::process::exit(0);
+            Message::KeyEvent(iced::keyboard::Event::KeyPressed { key, modifiers, text, .. }) => {
+                use iced::keyboard::{key::Named, Key};
+                if self.palette.is_some() {
+                    return self.palette_key(key.as_ref(), text.as_deref());
+                }
+                match key.as_ref() {
+                    Key::Named(Named::Escape) => {
+                        // Escape closes the Wi-Fi picker first, then the dashboard
+                        if self.wifi_open {
+                            self.wifi_open = false;
+                        } else {
+                            std::process::exit(0);
+                        }
+                    }
+                    Key::Character("/")                        => self.palette = Some(Palette::default()),
+                    Key::Character("k") if modifiers.control() => self.palette = Some(Palette::default()),
+                    _ => {}
+                }
+            }
+            Message::PaletteRun(idx) => {
+                let Some(item) = self.palette_matches().into_iter().nth(idx) else {
+                    return Task::none();
+                };
+                self.palette = None;
+                match item.action {
+                    PaletteAction::Send(msg) => return Task::done(*msg),
+                    PaletteAction::Dispatch(args) => {
+                        run_dispatch(&args);
+                        std::process::exit(0);
+                    }
+                    PaletteAction::Shell(cmd) => {
+                        if let Err(e) = std::process::Command::new("sh").args(["-c", &cmd]).spawn() {
+                            tracing::warn!("Palette command `{cmd}` could not start: {e}");
+                            return Task::none();
+                        }
+                        std::process::exit(0);
+                    }
                 }
             }
             Message::VolumeSet(v) => {
-                let clamped = v.clamp(0.0, 1.5);
+                let max = self.theme.volume_max;
+                let clamped = v.clamp(0.0, max);
                 self.sys.volume = Some(clamped);
-                let arg = format!("{clamped:.2}");
+                let arg   = format!("{clamped:.2}");
+                let limit = format!("{max:.2}");
                 tokio::spawn(async move {
                     let _ = tokio::process::Command::new("wpctl")
-                        .args(["set-volume", "@DEFAULT_AUDIO_SINK@", &arg])
+                        .args(["set-volume", "-l", &limit, "@DEFAULT_AUDIO_SINK@", &arg])
                         .output().await;
                 });
             }
             Message::BrightnessSet(pct) => {
                 self.sys.brightness = Some(pct);
-                tokio::spawn(async move {
-                    let _ = tokio::process::Command::new("brightnessctl")
-                        .args(["set", &format!("{pct}%")])
-                        .output().await;
-                });
+                if has_backlight() {
+                    tokio::spawn(async move {
+                        let _ = tokio::process::Command::new("brightnessctl")
+                            .args(["set", &format!("{pct}%")])
+                            .output().await;
+                    });
+                } else {
+                    set_ddc_brightness(pct);
+                }
             }
             Message::MediaAction(cmd) => {
                 if cmd == "play-pause" {
-                    self.sys.media_playing = !self.sys.media_playing;
+                    self.sys.media_playing     = !self.sys.media_playing;
+                    self.sys.media_state_since = Some(Instant::now());
                 }
+                let player = selected_player();
                 tokio::spawn(async move {
-                    let _ = tokio::process::Command::new("playerctl").arg(cmd).output().await;
+                    let _ = playerctl(player.as_deref(), &[cmd]).output().await;
                 });
             }
+            Message::ConfigReloaded(config) => {
+                let hooks = run_hooks("on_reload", &config.on_reload);
+                self.apply_config(*config);
+                return hooks;
+            }
+            Message::Notice(notice) => {
+                self.notice = Some(notice);
+            }
+            Message::NoticeDismiss => {
+                self.notice = None;
+            }
+            Message::ReducedMotion(reduce) if self.theme.reduce_motion_auto => {
+                self.theme.reduce_motion = reduce;
+                if reduce {
+                    self.intro_t = 1.0;
+                }
+            }
+            Message::HintDismiss => {
+                self.first_run_hint = false;
+            }
+            Message::OpenEditor => {
+                if let Err(e) = std::process::Command::new("bar-editor").spawn() {
+                    tracing::warn!("Cannot launch bar-editor: {e}");
+                    return Task::none();
+                }
+                std::process::exit(0);
+            }
+            Message::HyprDispatch(args) => {
+                tokio::task::spawn_blocking(move || run_dispatch(&args));
+            }
+            Message::OutputsPower(awake) => {
+                tracing::info!("Outputs {}", if awake { "woke up; resuming" } else { "are off; pausing" });
+                self.outputs_asleep = !awake;
+            }
+            Message::CaffeineToggle => {
+                self.caffeine = match self.caffeine.take() {
+                    Some(running) => {
+                        stop_caffeine(running);
+                        None
+                    }
+                    None => start_caffeine(self.caffeine_duration()),
+                };
+            }
+            Message::CaffeineCycle => {
+                if !self.caffeine_minutes.is_empty() {
+                    self.caffeine_preset = (self.caffeine_preset + 1) % self.caffeine_minutes.len();
+                }
+                if let Some(running) = self.caffeine.take() {
+                    stop_caffeine(running);
+                    self.caffeine = start_caffeine(self.caffeine_duration());
+                }
+            }
+            Message::DrawerToggle(idx) => {
+                let drawer = self.drawers.entry(idx).or_default();
+                drawer.open = !drawer.open;
+            }
+            Message::WifiToggle => {
+                self.wifi_open = !self.wifi_open;
+                let fresh = self.wifi_scanned_at.is_some_and(|at| at.elapsed() < WIFI_CACHE_TTL);
+                if self.wifi_open && !fresh && !self.wifi_busy {
+                    self.wifi_busy = true;
+                    return Task::perform(scan_wifi(self.wifi_backend.clone()), Message::WifiScanned);
+                }
+            }
+            Message::WifiScanned(result) => {
+                self.wifi_busy = false;
+                match result {
+                    Ok(nets) => {
+                        self.wifi_networks   = nets;
+                        self.wifi_scanned_at = Some(Instant::now());
+                    }
+                    Err(e) => self.wifi_status = Some(Err(e)),
+                }
+            }
+            Message::WifiConnect(ssid) if !self.wifi_busy => {
+                self.wifi_busy   = true;
+                self.wifi_status = Some(Ok(format!("Connecting to {ssid}\u{2026}")));
+                return Task::perform(
+                    connect_wifi(self.wifi_backend.clone(), ssid),
+                    Message::WifiConnected,
+                );
+            }
+            Message::WifiConnected(result) => {
+                self.wifi_busy   = false;
+                // Force a rescan next time so the active network is current
+                self.wifi_scanned_at = None;
+                self.wifi_status = Some(result);
+            }
+            Message::CardScroll(kind, delta) => {
+                let accel = self.theme.scroll_acceleration;
+                match kind {
+                    "volume" => {
+                        let steps = self.volume_scroll.feed(delta, accel);
+                        if steps != 0 {
+                            let step = self.theme.volume_step as f32 / 100.0;
+                            let vol  = self.sys.volume.unwrap_or(0.0) + steps as f32 * step;
+                            return Task::done(Message::VolumeSet(vol));
+                        }
+                    }
+                    "brightness" => {
+                        let steps = self.brightness_scroll.feed(delta, accel);
+                        if steps != 0 {
+                            let step = self.theme.brightness_step as i32;
+                            let pct  = self.sys.brightness.unwrap_or(50) as i32 + steps * step;
+                            return Task::done(Message::BrightnessSet(pct.clamp(0, 100) as u8));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Message::MediaCyclePlayer => {
+                let players = &self.sys.media_players;
+                if !players.is_empty() {
+                    let current = selected_player();
+                    let next = match current.and_then(|c| players.iter().position(|p| *p == c)) {
+                        Some(i) => (i + 1) % players.len(),
+                        None    => 0,
+                    };
+                    select_player(Some(players[next].clone()));
+                }
+            }
             Message::PowerAction(action) => {
                 let cmd_str = match action {
                     "lock"      => self.lock_command.clone(),
                     "sleep"     => "systemctl suspend".to_string(),
                     "hibernate" => "systemctl hibernate".to_string(),
-                    "logout"    => "hyprctl dispatch exit".to_string(),
+                    "logout"    => {
+                        run_dispatch("exit");
+                        std::process::exit(0);
+                    }
                     "reboot"    => "systemctl reboot".to_string(),
                     "shutdown"  => "systemctl poweroff".to_string(),
                     _           => return Task::none(),
@@ -629,11 +2361,34 @@ impl Dashboard {
                 std::process::exit(0);
             }
             Message::AnimFrame => {
+                self.metrics.last_frame = Some(Instant::now());
                 self.eq_tick = self.eq_tick.wrapping_add(1);
                 // Advance entrance animation until fully visible
+                // Without animations everything jumps to its end state
+                let (intro_dt, drawer_dt) =
+                    if self.theme.animate() { (INTRO_DT, DRAWER_DT) } else { (1.0, 1.0) };
                 if self.intro_t < 1.0 {
-                    self.intro_t = (self.intro_t + INTRO_DT).min(1.0);
+                    self.intro_t = (self.intro_t + intro_dt).min(1.0);
+                }
+                if let Some(done) = self.caffeine.filter(|c| c.until <= Instant::now()) {
+                    stop_caffeine(done);
+                    self.caffeine = None;
+                }
+                for drawer in self.drawers.values_mut() {
+                    drawer.t = if drawer.open {
+                        (drawer.t + drawer_dt).min(1.0)
+                    } else {
+                        (drawer.t - drawer_dt).max(0.0)
+                    };
+                }
+                let stale = self.is_stale();
+                if stale && !self.stale_logged {
+                    tracing::error!(
+                        "System snapshot is older than {}s; the monitor may have stalled.",
+                        STALE_AFTER.as_secs()
+                    );
                 }
+                self.stale_logged = stale;
             }
             _ => {}
         }
@@ -649,58 +2404,27 @@ impl Dashboard {
 
         // Improved overlay background: use theme background color (tinted dark),
         // rather than pure black, for a cohesive frosted-glass feel.
-        let bg_iced = t.background.to_iced();
-        let overlay_bg = Color {
-            r: bg_iced.r * 0.55,
-            g: bg_iced.g * 0.55,
-            b: bg_iced.b * 0.55,
-            a: 0.88,
-        };
+        let overlay_bg = t.background.mix(ThemeColor::BLACK, 0.45).with_alpha(0.88).to_iced();
 
         // Span-aware bento grid — wide cards (clock/media/power) span 2 columns.
         let cols = self.dash_config.columns.clamp(2, 4) as usize;
         // Increased gap for better whitespace between cards
         let gap  = 18.0f32;
 
-        let mut grid_rows: Vec<Element<'_, Message>> = Vec::new();
-        let mut row_items: Vec<Element<'_, Message>> = Vec::new();
-        let mut row_span = 0usize;
-        let mut card_idx = 0usize;
-
-        for item in &self.dash_config.items {
-            let kind = item.kind.as_str();
-            // Use col_span from config if > 1, otherwise fall back to card_span() default.
-            let span = if item.col_span > 1 {
-                (item.col_span as usize).min(cols)
-            } else {
-                card_span(kind).min(cols)
-            };
-            if row_span + span > cols && !row_items.is_empty() {
-                grid_rows.push(
-                    iced::widget::Row::from_vec(std::mem::take(&mut row_items))
-                        .spacing(gap).align_y(Alignment::Center).into(),
-                );
-                row_span = 0;
-            }
-            if let Some(card) = self.make_card(kind, span, card_idx) {
-                row_items.push(card);
-                row_span += span;
-                card_idx += 1;
+        // Cards with nothing to show this frame take no room in the grid.
+        let mut spans = Vec::new();
+        let mut cards = Vec::new();
+        for (slot, item) in self.dash_config.items.iter().enumerate().filter(|(_, c)| c.enabled) {
+            let span = item_span(item, cols);
+            if let Some(card) = self.make_card(item, span, cards.len(), slot, false) {
+                spans.push(span);
+                cards.push(Some(card));
             }
-            if row_span >= cols {
-                grid_rows.push(
-                    iced::widget::Row::from_vec(std::mem::take(&mut row_items))
-                        .spacing(gap).align_y(Alignment::Center).into(),
-                );
-                row_span = 0;
-            }
-        }
-        if !row_items.is_empty() {
-            grid_rows.push(
-                iced::widget::Row::from_vec(row_items)
-                    .spacing(gap).align_y(Alignment::Center).into(),
-            );
         }
+        let grid_rows: Vec<Element<'_, Message>> = grid_layout(&spans, cols, self.rtl())
+            .into_iter()
+            .map(|row| grid_row(row.into_iter().filter_map(|i| cards[i].take()).collect(), gap))
+            .collect();
 
         let grid = iced::widget::Column::from_vec(grid_rows)
             .spacing(gap)
@@ -730,7 +2454,24 @@ impl Dashboard {
         .align_y(Alignment::Center)
         .spacing(0.0);
 
-        let content_col = column![grid, hint]
+        // The palette takes the grid's place while it's open
+        let main: Element<'_, Message> = if self.palette.is_some() {
+            self.palette_panel()
+        } else {
+            grid.into()
+        };
+        let mut content_items: Vec<Element<'_, Message>> = vec![main];
+        if self.first_run_hint {
+            content_items.push(self.first_run_panel());
+        }
+        if let Some(notice) = &self.notice {
+            content_items.push(self.notice_panel(notice));
+        }
+        if self.wifi_open {
+            content_items.push(self.wifi_panel());
+        }
+        content_items.push(hint.into());
+        let content_col = iced::widget::Column::from_vec(content_items)
             .spacing(28.0)
             .align_x(Alignment::Center);
 
@@ -773,7 +2514,18 @@ impl Dashboard {
         }
     }
 
-    fn make_card(&self, item: &str, span: usize, card_idx: usize) -> Option<Element<'_, Message>> {
+    /// Build the card for `card`, the `card_idx`th shown and at `slot` in
+    /// `dashboard.items`.  With `bare`, only its content is returned — no
+    /// background, border or padding — for use inside a group card.
+    fn make_card(
+        &self,
+        card: &CardConfig,
+        span: usize,
+        card_idx: usize,
+        slot: usize,
+        bare: bool,
+    ) -> Option<Element<'_, Message>> {
+        let item   = card.kind.as_str();
         let t      = &self.theme;
         let fsize  = t.font_size;
         let fg     = t.foreground.to_iced();
@@ -781,8 +2533,11 @@ impl Dashboard {
         let theme  = self.dash_config.theme.as_str();
         let nerd   = t.use_nerd_icons;
 
+        // Cards fed by the snapshot dim when it goes stale.
+        let stale = self.is_stale() && !matches!(item, "clock" | "power" | "host" | "debug" | "group");
+
         // Per-card entrance opacity (staggered)
-        let opacity = self.card_opacity(card_idx);
+        let opacity = self.card_opacity(card_idx) * if stale { 0.45 } else { 1.0 };
 
         // Bold font for primary values
         let bold_font = Font {
@@ -798,19 +2553,44 @@ impl Dashboard {
         };
         let gap = 18.0f32;
         let card_w = if span >= 2 { base_w * span as f32 + gap * (span - 1) as f32 } else { base_w };
-        let card_h = match item {
-            "clock" | "media" => base_h * 1.25,
-            "load"            => base_h * 1.10,
-            _                 => base_h,
+        let card_w = card_w.max(card.min_width);
+
+        // Group children keep their own widths, side by side; a closed drawer
+        // shows only the first and slides the rest out as it opens.
+        let children: Vec<&CardConfig> = card.children.iter().filter(|c| c.enabled && c.kind != "group").collect();
+        let child_w = |c: &CardConfig| base_w.max(c.min_width);
+        let group_full_w = children.iter().map(|c| child_w(c)).sum::<f32>()
+            + children.len().saturating_sub(1) as f32 * (1.0 + 2.0 * GROUP_SEP_PAD);
+        let group_visible_w = match children.first() {
+            Some(first) if card.drawer => {
+                let t = self.drawers.get(&slot).map_or(0.0, |d| d.t);
+                let eased = 1.0 - (1.0 - t).powi(3);
+                child_w(first) + (group_full_w - child_w(first)) * eased
+            }
+            _ => group_full_w,
         };
+        let card_w = if item == "group" { group_visible_w + 32.0 } else { card_w };
 
-        let bg_iced = t.background.to_iced();
-        // Glassmorphism: lighter card bg with moderate transparency
-        let card_bg_base = Color {
-            r: (bg_iced.r + 0.06).min(1.0),
-            g: (bg_iced.g + 0.06).min(1.0),
-            b: (bg_iced.b + 0.08).min(1.0),
-            a: 0.75 * opacity,
+        let card_h = if item == "group" {
+            base_h * children.iter().map(|c| card_height(&c.kind)).fold(1.0, f32::max)
+        } else {
+            base_h * card_height(item)
+        };
+
+        // Spacers only occupy their grid slot — no background, border or padding.
+        if item == "spacer" {
+            return Some(
+                iced::widget::Space::new()
+                    .width(Length::Fixed(card_w))
+                    .height(Length::Fixed(card_h))
+                    .into(),
+            );
+        }
+
+        // Glassmorphism: lighter card bg (slightly blue-shifted) with moderate transparency
+        let card_bg_base = {
+            let base = t.background.lighten(0.06);
+            ThemeColor { b: (base.b + 0.02).min(1.0), ..base }.with_alpha(0.75 * opacity).to_iced()
         };
         // Per-item semantic tint — barely perceptible, just enough to hint at
         // the card's "zone" without clashing with the overall palette.
@@ -847,10 +2627,17 @@ impl Dashboard {
 
             // ── Clock ─────────────────────────────────────────────────────────
             "clock" => {
-                let now = chrono::Local::now();
-                let time_str = now.format(t.clock_format.as_str()).to_string();
-                let date_str = now.format(t.date_format.as_str()).to_string();
+                let (time_str, date_str) =
+                    clock_label(&chrono::Local::now(), &t.clock_format, &t.date_format);
                 let accent_cap = accent;
+                // The date line, unless it's been moved to the hover tooltip
+                let date_line = || -> Element<'static, Message> {
+                    if t.clock_show_date {
+                        text(date_str.clone()).size(fsize - 1.0).color(sec_col).into()
+                    } else {
+                        iced::widget::Space::new().into()
+                    }
+                };
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(time_str).size(fsize + 4.0).color(val_col),
@@ -872,7 +2659,7 @@ impl Dashboard {
                             .size(fsize + 14.0)
                             .font(bold_font)
                             .color(Color { a: opacity, ..fg }),
-                        text(date_str).size(fsize - 1.0).color(sec_col),
+                        date_line(),
                         accent_line,
                     ].spacing(4.0).align_x(Alignment::Center).into()
                 } else {
@@ -881,23 +2668,32 @@ impl Dashboard {
                             .size(fsize + 14.0)
                             .font(bold_font)
                             .color(Color { a: opacity, ..fg }),
-                        text(date_str).size(fsize - 1.0).color(sec_col),
+                        date_line(),
                     ].spacing(4.0).align_x(Alignment::Center).into()
                 };
+                // Minimal never shows the date inline, so it always gets the tooltip
+                let content = if t.clock_show_date && theme != "minimal" {
+                    content
+                } else {
+                    let bg = t.background.to_iced();
+                    let tip = container(text(date_str).size(fsize - 1.0).color(fg))
+                        .padding([4.0, 8.0])
+                        .style(move |_: &iced::Theme| iced::widget::container::Style {
+                            background: Some(Background::Color(Color { a: 0.92, ..bg })),
+                            border: Border { radius: 6.0.into(), ..Default::default() },
+                            ..Default::default()
+                        });
+                    iced::widget::tooltip(content, tip, iced::widget::tooltip::Position::Bottom).into()
+                };
                 (content, accent)
             }
 
             // ── Network ───────────────────────────────────────────────────────
             "network" => {
                 let blue = Color::from_rgba(0.54, 0.71, 0.98, opacity);
-                let iface = if self.sys.net_iface.is_empty() {
-                    "No network".to_string()
-                } else {
-                    self.sys.net_iface.clone()
-                };
-                let icon = if nerd { "\u{f05a9}" } else { "NET" };
-                let rx_str = format!("↓ {}", fmt_bytes(self.sys.net_rx_bps));
-                let tx_str = format!("↑ {}", fmt_bytes(self.sys.net_tx_bps));
+                let (icon, iface) = network_label(&self.sys, nerd, &t.network_signal);
+                let rx_str = format!("↓ {}", self.fmt_rate(self.sys.net_rx_bps));
+                let tx_str = format!("↑ {}", self.fmt_rate(self.sys.net_tx_bps));
 
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
@@ -937,8 +2733,7 @@ impl Dashboard {
 
             // ── Battery ───────────────────────────────────────────────────────
             "battery" => {
-                let pct = self.sys.battery_pct?;
-                let charging = self.sys.battery_charging;
+                let (pct, charging) = self.sys.battery()?;
                 let warn = t.battery_warn_percent;
                 let fill_col = if charging {
                     Color::from_rgba(0.67, 0.88, 0.63, opacity)
@@ -947,11 +2742,8 @@ impl Dashboard {
                 } else {
                     Color { a: 0.85 * opacity, ..fg }
                 };
-                let icon = if charging {
-                    if nerd { "\u{f0e7}" } else { "⚡" }
-                } else if nerd { "\u{f0079}" } else { "BAT" };
+                let (icon, pct_str) = battery_label(&self.sys, nerd, t.pad_numbers)?;
                 let frac = pct as f32 / 100.0;
-                let pct_str = format!("{pct}%");
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(fill_col),
@@ -976,8 +2768,7 @@ impl Dashboard {
                     Color::from_rgba(0.96, 0.54, 0.67, opacity),
                     (frac * 2.0 - 1.0).max(0.0),
                 );
-                let icon = if nerd { "\u{f4bc}" } else { "CPU" };
-                let val  = format!("{:.0}%", self.sys.cpu_pct);
+                let (icon, val) = cpu_label(&self.sys, nerd, t.pad_numbers);
 
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
@@ -1018,13 +2809,9 @@ impl Dashboard {
 
             // ── Memory ────────────────────────────────────────────────────────
             "memory" => {
-                let frac = if self.sys.ram_total > 0 {
-                    self.sys.ram_used as f32 / self.sys.ram_total as f32
-                } else { 0.0 };
+                let frac = self.sys.ram_frac();
                 let mem_col = Color::from_rgba(0.79, 0.65, 0.97, opacity);
-                let icon = if nerd { "\u{f035b}" } else { "RAM" };
-                let val  = fmt_bytes(self.sys.ram_used);
-                let sub  = format!("/ {}", fmt_bytes(self.sys.ram_total));
+                let (icon, val, sub) = memory_label(&self.sys, nerd, |b| self.fmt_size(b));
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(mem_col),
@@ -1045,10 +2832,9 @@ impl Dashboard {
             // ── Swap ──────────────────────────────────────────────────────────
             "swap" => {
                 if self.sys.swap_total == 0 { return None; }
-                let frac = self.sys.swap_used as f32 / self.sys.swap_total as f32;
+                let frac = self.sys.swap_frac();
                 let swap_col = Color::from_rgba(0.96, 0.69, 0.98, opacity);
-                let icon = if nerd { "\u{f0552}" } else { "SWP" };
-                let val  = format!("{} / {}", fmt_bytes(self.sys.swap_used), fmt_bytes(self.sys.swap_total));
+                let (icon, val) = swap_label(&self.sys, nerd, |b| self.fmt_size(b));
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(swap_col),
@@ -1068,10 +2854,7 @@ impl Dashboard {
             // ── Load average ──────────────────────────────────────────────────
             "load" => {
                 let load_col = Color::from_rgba(0.98, 0.81, 0.68, opacity);
-                let icon = if nerd { "\u{f080}" } else { "LOAD" };
-                let l1  = format!("{:.2}", self.sys.load_1);
-                let l5  = format!("{:.2}", self.sys.load_5);
-                let l15 = format!("{:.2}", self.sys.load_15);
+                let (icon, [l1, l5, l15]) = load_label(&self.sys, nerd, |s| self.fmt_num(s));
 
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
@@ -1103,21 +2886,10 @@ impl Dashboard {
 
             // ── GPU ───────────────────────────────────────────────────────────
             "gpu" => {
-                let pct = self.sys.gpu_percent?;
-                let frac = pct / 100.0;
+                let (icon, pct_str, temp_str, mem_str) =
+                    gpu_label(&self.sys, nerd, t.pad_numbers, &t.temperature_unit, |b| self.fmt_size(b))?;
+                let frac = self.sys.gpu_percent.unwrap_or(0.0) / 100.0;
                 let gpu_col = Color::from_rgba(0.54, 0.87, 0.75, opacity);
-                let icon = if nerd { "\u{f071b}" } else { "GPU" };
-
-                let pct_str  = format!("{pct:.0}%");
-                let temp_str = self.sys.gpu_temp
-                    .map(|t| format!("{t:.0}°C"))
-                    .unwrap_or_default();
-                let mem_str = match (self.sys.gpu_mem_used, self.sys.gpu_mem_total) {
-                    (Some(u), Some(t)) if t > 0 => {
-                        format!("{} / {}", fmt_bytes(u), fmt_bytes(t))
-                    }
-                    _ => String::new(),
-                };
 
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
@@ -1149,13 +2921,13 @@ impl Dashboard {
 
             // ── Disk ──────────────────────────────────────────────────────────
             "disk" => {
-                let frac = if self.sys.disk_total > 0 {
-                    self.sys.disk_used as f32 / self.sys.disk_total as f32
-                } else { 0.0 };
-                let disk_col = Color::from_rgba(0.98, 0.89, 0.68, opacity);
-                let icon = if nerd { "\u{f01bc}" } else { "DSK" };
-                let val  = fmt_bytes(self.sys.disk_used);
-                let sub  = format!("/ {}", fmt_bytes(self.sys.disk_total));
+                let frac = self.sys.disk_frac();
+                let disk_col = match disk_level(frac, t) {
+                    Level::Critical => Color { a: opacity, ..t.critical.to_iced() },
+                    Level::Warning  => Color { a: opacity, ..t.warning.to_iced() },
+                    Level::Normal   => Color::from_rgba(0.98, 0.89, 0.68, opacity),
+                };
+                let (icon, val, sub) = disk_label(&self.sys, nerd, |b| self.fmt_size(b));
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(disk_col),
@@ -1176,16 +2948,19 @@ impl Dashboard {
             // ── Volume ────────────────────────────────────────────────────────
             "volume" => {
                 let vol     = self.sys.volume.unwrap_or(0.0);
-                let _frac   = (vol / 1.5).clamp(0.0, 1.0);
-                let vol_col = Color::from_rgba(0.58, 0.89, 0.84, opacity);
-                let icon = if self.sys.volume_muted {
-                    if nerd { "\u{f075f}" } else { "M" }
-                } else if nerd { "\u{f057e}" } else { "V" };
-                let val = format!("{:.0}%", vol * 100.0);
+                // Over-amplified (>100%) output is shown in red as a warning.
+                let vol_col = if vol > 1.0 {
+                    Color::from_rgba(0.96, 0.54, 0.67, opacity)
+                } else {
+                    Color::from_rgba(0.58, 0.89, 0.84, opacity)
+                };
+                let (icon, vol_label, val) =
+                    volume_label(&self.sys, nerd, t.volume_show_port, t.pad_numbers);
                 let vol_cap = vol_col;
                 let fg_cap  = fg;
                 let slider_elem: Element<'_, Message> = if theme != "minimal" {
-                    iced::widget::slider(0.0f32..=1.5, vol, Message::VolumeSet)
+                    iced::widget::slider(0.0f32..=t.volume_max, vol.min(t.volume_max), Message::VolumeSet)
+                        .step(t.volume_step as f32 / 100.0)
                         .width(Length::Fixed(bar_w))
                         .style(move |_: &iced::Theme, _| iced::widget::slider::Style {
                             rail: iced::widget::slider::Rail {
@@ -1215,7 +2990,7 @@ impl Dashboard {
                 } else {
                     column![
                         text(icon).size(fsize + 10.0).color(vol_col),
-                        text("Volume").size(fsize - 2.0).color(label_col),
+                        text(vol_label).size(fsize - 2.0).color(label_col),
                         text(val).size(fsize + 4.0).font(bold_font).color(vol_col),
                         slider_elem,
                     ].spacing(6.0).align_x(Alignment::Center).into()
@@ -1228,8 +3003,7 @@ impl Dashboard {
                 let bright  = self.sys.brightness.unwrap_or(50);
                 let _frac   = bright as f32 / 100.0;
                 let br_col  = Color::from_rgba(0.98, 0.89, 0.55, opacity);
-                let icon = if nerd { "\u{f00e0}" } else { "BRT" };
-                let val  = format!("{bright}%");
+                let (icon, val) = brightness_label(&self.sys, nerd, t.pad_numbers);
                 let br_cap = br_col;
                 let fg_cap = fg;
                 let slider_elem: Element<'_, Message> = if theme != "minimal" {
@@ -1238,6 +3012,7 @@ impl Dashboard {
                         bright as f32,
                         |v| Message::BrightnessSet(v.round() as u8),
                     )
+                    .step(t.brightness_step as f32)
                     .width(Length::Fixed(bar_w))
                     .style(move |_: &iced::Theme, _| iced::widget::slider::Style {
                         rail: iced::widget::slider::Rail {
@@ -1277,22 +3052,22 @@ impl Dashboard {
 
             // ── Media ─────────────────────────────────────────────────────────
             "media" => {
+                let paused_for = self.sys.media_state_since.map(|since| since.elapsed());
+                if let (false, Some(after), Some(paused)) =
+                    (self.sys.media_playing, self.media_hide_paused_after, paused_for)
+                {
+                    if paused >= after {
+                        return None;
+                    }
+                }
                 let pink = Color::from_rgba(0.96, 0.54, 0.84, opacity);
-                let play_icon = if nerd {
-                    if self.sys.media_playing { "\u{f03e4}" } else { "\u{f040a}" }
-                } else if self.sys.media_playing { "⏸" } else { "▶" };
-                let prev_icon = if nerd { "\u{f0602}" } else { "⏮" };
-                let next_icon = if nerd { "\u{f0604}" } else { "⏭" };
-
-                let title = self.sys.media_title.as_deref().unwrap_or("Nothing playing");
-                let trunc = if title.len() > 20 { &title[..20] } else { title };
-                let trunc = trunc.to_string();
-
-                let artist = self.sys.media_artist.as_deref().unwrap_or("").to_string();
+                let ([prev_icon, play_icon, next_icon], trunc, artist) =
+                    media_label(&self.sys, nerd, t.media_max_chars);
 
                 // Smooth sine-wave equalizer bars
                 let eq: Element<'_, Message> = if self.sys.media_playing && theme != "minimal" {
-                    let tick = self.eq_tick as f32;
+                    // Frozen in place with reduce_motion
+                    let tick = if t.animate() { self.eq_tick as f32 } else { 0.0 };
                     let pink_cap = pink;
                     let bars: Vec<Element<'_, Message>> = (0..5).map(|i| {
                         // Smooth sine wave: each bar has a different phase offset
@@ -1375,9 +3150,33 @@ impl Dashboard {
                         text(trunc).size(fsize - 1.0).color(val_col),
                     ].spacing(6.0).align_y(Alignment::Center).into()
                 } else {
-                    let mut col_items: Vec<Element<'_, Message>> = vec![
+                    let mut col_items: Vec<Element<'_, Message>> = Vec::new();
+                    // Player name — clickable to cycle when several are running
+                    if !self.sys.media_players.is_empty() {
+                        let players = &self.sys.media_players;
+                        let name = match selected_player().filter(|p| players.contains(p)) {
+                            Some(p)                    => player_label(&p).to_string(),
+                            None if players.len() == 1 => player_label(&players[0]).to_string(),
+                            None                       => format!("{} players", players.len()),
+                        };
+                        let label = text(name)
+                            .size(fsize - 3.5)
+                            .color(Color { a: 0.8 * opacity, ..pink });
+                        if self.sys.media_players.len() > 1 {
+                            col_items.push(
+                                iced::widget::button(label)
+                                    .on_press(Message::MediaCyclePlayer)
+                                    .padding([0.0, 4.0])
+                                    .style(|_: &iced::Theme, _| iced::widget::button::Style::default())
+                                    .into(),
+                            );
+                        } else {
+                            col_items.push(label.into());
+                        }
+                    }
+                    col_items.push(
                         text(trunc).size(fsize - 1.0).font(bold_font).color(val_col).into(),
-                    ];
+                    );
                     if !artist.is_empty() {
                         col_items.push(
                             text(artist).size(fsize - 2.5).color(sec_col).into()
@@ -1443,8 +3242,7 @@ impl Dashboard {
             // ── Uptime ────────────────────────────────────────────────────────
             "uptime" => {
                 let teal = Color::from_rgba(0.58, 0.89, 0.84, opacity);
-                let icon = if nerd { "\u{f150e}" } else { "UP" };
-                let val  = fmt_uptime(self.sys.uptime_secs);
+                let (icon, val) = uptime_label(&self.sys, nerd);
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(teal),
@@ -1460,17 +3258,39 @@ impl Dashboard {
                 (content, teal)
             }
 
-            // ── Temperature ───────────────────────────────────────────────────
-            "temperature" => {
-                let temp = self.sys.temp_celsius?;
+            // ── Caffeine ──────────────────────────────────────────────────────
+            "caffeine" => {
+                let amber = Color::from_rgba(0.92, 0.76, 0.60, opacity);
+                let icon  = if nerd { "\u{f0176}" } else { "CAF" };
+                let (val, icon_col) = match self.caffeine {
+                    Some(c) => (fmt_countdown(c.until.saturating_duration_since(Instant::now())), amber),
+                    None    => (format!("Off \u{00b7} {}", fmt_minutes(self.caffeine_duration())), label_col),
+                };
+                let content: Element<'_, Message> = if theme == "minimal" {
+                    row![
+                        text(icon).size(fsize).color(icon_col),
+                        text(val).size(fsize).color(val_col),
+                    ].spacing(6.0).align_y(Alignment::Center).into()
+                } else {
+                    column![
+                        text(icon).size(fsize + 10.0).color(icon_col),
+                        text("Caffeine").size(fsize - 2.0).color(label_col),
+                        text(val).size(fsize + 4.0).font(bold_font).color(val_col),
+                    ].spacing(4.0).align_x(Alignment::Center).into()
+                };
+                (content, amber)
+            }
+
+            // ── Temperature ───────────────────────────────────────────────────
+            "temperature" => {
+                let temp = self.sys.temp_celsius?;
                 let heat = ((temp - 40.0) / 50.0).clamp(0.0, 1.0);
                 let temp_col = lerp_color(
                     Color::from_rgba(0.67, 0.88, 0.63, opacity),
                     Color::from_rgba(0.96, 0.54, 0.67, opacity),
                     heat,
                 );
-                let icon = if nerd { "\u{f050f}" } else { "TMP" };
-                let val  = format!("{temp:.0}°C");
+                let (icon, val) = temperature_label(&self.sys, nerd, &t.temperature_unit)?;
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(temp_col),
@@ -1489,12 +3309,7 @@ impl Dashboard {
             // ── Updates ───────────────────────────────────────────────────────
             "updates" => {
                 let yellow = Color::from_rgba(0.98, 0.70, 0.53, opacity);
-                let icon = if nerd { "\u{f0954}" } else { "UPD" };
-                let val = match self.sys.update_count {
-                    Some(0) => "Up to date".to_string(),
-                    Some(n) => format!("{n} updates"),
-                    None    => "Checking\u{2026}".to_string(),
-                };
+                let (icon, val) = updates_label(&self.sys, nerd);
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
                         text(icon).size(fsize).color(yellow),
@@ -1513,15 +3328,8 @@ impl Dashboard {
             // ── Bluetooth ─────────────────────────────────────────────────────
             "bluetooth" => {
                 let bt_col = Color::from_rgba(0.49, 0.72, 0.97, opacity);
-                let icon = if nerd { "\u{f00af}" } else { "BT" };
-                let (status_str, device_str) = if self.sys.bt_connected {
-                    let dev = self.sys.bt_device_name.as_deref()
-                        .unwrap_or("Connected");
-                    let dev_trunc = if dev.len() > 14 { &dev[..14] } else { dev };
-                    ("Connected".to_string(), dev_trunc.to_string())
-                } else {
-                    ("Disconnected".to_string(), String::new())
-                };
+                let (icon, status_str, device_str) =
+                    bluetooth_label(&self.sys, nerd, t.bluetooth_max_chars);
                 let status_col = if self.sys.bt_connected {
                     bt_col
                 } else {
@@ -1549,28 +3357,57 @@ impl Dashboard {
                 (content, bt_col)
             }
 
+            // ── Peripherals ───────────────────────────────────────────────────
+            "peripherals" => {
+                let limit = self.peripherals_only_below.unwrap_or(100);
+                let shown: Vec<&Peripheral> = self.sys.peripherals.iter().filter(|p| p.pct <= limit).collect();
+                if shown.is_empty() {
+                    return None;
+                }
+                let per_col = Color::from_rgba(0.65, 0.89, 0.63, opacity);
+                let level_col = |pct: u8| {
+                    let c = if pct <= 10 {
+                        t.critical.to_iced()
+                    } else if pct <= 25 {
+                        t.warning.to_iced()
+                    } else {
+                        val_col
+                    };
+                    Color { a: opacity, ..c }
+                };
+                let rows: Vec<Element<'_, Message>> = shown
+                    .iter()
+                    .take(if theme == "minimal" { 2 } else { 4 })
+                    .map(|p| {
+                        row![
+                            text(peripheral_icon(&p.kind, nerd)).size(fsize).color(per_col),
+                            text(truncate_smart(&p.name, t.bluetooth_max_chars, "\u{2026}"))
+                                .size(fsize - 2.0).color(sec_col),
+                            text(fmt_pct(p.pct as f32, t.pad_numbers))
+                                .size(fsize - 1.0).font(bold_font).color(level_col(p.pct)),
+                        ].spacing(6.0).align_y(Alignment::Center).into()
+                    })
+                    .collect();
+                let content: Element<'_, Message> = if theme == "minimal" {
+                    iced::widget::Row::from_vec(rows).spacing(10.0).align_y(Alignment::Center).into()
+                } else {
+                    let mut items: Vec<Element<'_, Message>> = vec![
+                        text("Devices").size(fsize - 2.0).color(label_col).into(),
+                    ];
+                    items.extend(rows);
+                    iced::widget::Column::from_vec(items)
+                        .spacing(4.0).align_x(Alignment::Center).into()
+                };
+                (content, per_col)
+            }
+
             // ── Weather ───────────────────────────────────────────────────────
             "weather" => {
                 // Hide if location not configured or weather not yet fetched
                 if self.weather_location.is_empty() { return None; }
 
                 let sky_col = Color::from_rgba(0.53, 0.82, 0.96, opacity);
-                let icon = if nerd { "\u{f0599}" } else { "WX" };
-
-                let (weather_main, weather_detail) = if self.sys.weather_text.is_empty() {
-                    ("Fetching\u{2026}".to_string(), String::new())
-                } else {
-                    // wttr.in format 3 returns: "CityName: ConditionIcon Temp"
-                    // e.g. "London: ⛅️  +12°C"
-                    let raw = &self.sys.weather_text;
-                    if let Some(colon_pos) = raw.find(':') {
-                        let detail = raw[colon_pos + 1..].trim().to_string();
-                        let city   = raw[..colon_pos].trim().to_string();
-                        (city, detail)
-                    } else {
-                        (raw.clone(), String::new())
-                    }
-                };
+                let (icon, weather_main, weather_detail) = weather_label(&self.sys, nerd);
 
                 let content: Element<'_, Message> = if theme == "minimal" {
                     row![
@@ -1594,9 +3431,190 @@ impl Dashboard {
                 (content, sky_col)
             }
 
+            // ── Self-report (dashboard's own resource usage) ──────────────────
+            "self" => {
+                let self_col = Color::from_rgba(0.71, 0.75, 0.86, opacity);
+                let icon = if nerd { "\u{f029a}" } else { "BAR" };
+                let cpu_str = self.fmt_num(format!("{:.1}% CPU", self.sys.self_cpu_pct));
+                let rss_str = format!("{} RSS", self.fmt_size(self.sys.self_rss));
+                let content: Element<'_, Message> = if theme == "minimal" {
+                    row![
+                        text(icon).size(fsize).color(self_col),
+                        text(cpu_str).size(fsize - 1.0).color(val_col),
+                    ].spacing(6.0).align_y(Alignment::Center).into()
+                } else {
+                    column![
+                        text(icon).size(fsize + 10.0).color(self_col),
+                        text("Dashboard").size(fsize - 2.0).color(label_col),
+                        text(cpu_str).size(fsize - 1.0).font(bold_font).color(val_col),
+                        text(rss_str).size(fsize - 2.5).color(sec_col),
+                    ].spacing(4.0).align_x(Alignment::Center).into()
+                };
+                (content, self_col)
+            }
+
+            // ── Templated metrics ─────────────────────────────────────────────
+            "metrics" => {
+                let met_col = Color::from_rgba(0.79, 0.65, 0.97, opacity);
+                let template = if card.template.is_empty() {
+                    "CPU {cpu}  MEM {ram_pct}"
+                } else {
+                    card.template.as_str()
+                };
+                let line = expand_template(template, |name| self.metric_value(name));
+                let content: Element<'_, Message> = if theme == "minimal" {
+                    text(line).size(fsize).color(val_col).into()
+                } else {
+                    column![
+                        text("Metrics").size(fsize - 2.0).color(label_col),
+                        text(line)
+                            .size(fsize + 1.0)
+                            .font(bold_font)
+                            .color(val_col)
+                            .align_x(Alignment::Center),
+                    ].spacing(6.0).align_x(Alignment::Center).into()
+                };
+                (content, met_col)
+            }
+
+            // ── Debug metrics ─────────────────────────────────────────────────
+            "debug" => {
+                let m = &self.metrics;
+                let dbg_col = Color::from_rgba(0.65, 0.68, 0.78, opacity);
+                let icon = if nerd { "\u{f00e4}" } else { "DBG" };
+                let msgs_str = format!("{} msgs", m.total());
+                let lat_str  = format!(
+                    "read {}ms \u{b7} lag {}ms",
+                    m.read_time.as_millis(),
+                    m.delivery.as_millis(),
+                );
+                let age_str = match m.stalest() {
+                    Some((name, age)) => format!("{name} {:.1}s ago", age.as_secs_f32()),
+                    None              => "no data yet".to_string(),
+                };
+                let content: Element<'_, Message> = if theme == "minimal" {
+                    row![
+                        text(icon).size(fsize).color(dbg_col),
+                        text(lat_str).size(fsize - 1.0).color(val_col),
+                    ].spacing(6.0).align_y(Alignment::Center).into()
+                } else {
+                    column![
+                        text(icon).size(fsize + 10.0).color(dbg_col),
+                        text(msgs_str).size(fsize - 2.0).color(label_col),
+                        text(lat_str).size(fsize - 1.0).font(bold_font).color(val_col),
+                        text(age_str).size(fsize - 2.5).color(sec_col),
+                    ].spacing(4.0).align_x(Alignment::Center).into()
+                };
+                (content, dbg_col)
+            }
+
+            // ── Host identity ─────────────────────────────────────────────────
+            "host" => {
+                let info = &self.host_info;
+                // Remote (SSH) sessions get a warm color so they stand out.
+                let host_col = if info.remote {
+                    Color::from_rgba(0.98, 0.70, 0.53, opacity)
+                } else {
+                    Color::from_rgba(0.58, 0.89, 0.84, opacity)
+                };
+                let icon = match (info.remote, nerd) {
+                    (true, true)   => "\u{f233}",
+                    (false, true)  => "\u{f108}",
+                    (true, false)  => "SSH",
+                    (false, false) => "HOST",
+                };
+                let val = if t.host_show == "host" || info.user.is_empty() {
+                    info.host.clone()
+                } else {
+                    format!("{}@{}", info.user, info.host)
+                };
+                let label = if info.remote { "Remote" } else { "Host" };
+                let content: Element<'_, Message> = if theme == "minimal" {
+                    row![
+                        text(icon).size(fsize).color(host_col),
+                        text(val).size(fsize - 1.0).color(val_col),
+                    ].spacing(6.0).align_y(Alignment::Center).into()
+                } else {
+                    column![
+                        text(icon).size(fsize + 10.0).color(host_col),
+                        text(label).size(fsize - 2.0).color(label_col),
+                        text(val).size(fsize - 1.0).font(bold_font).color(val_col),
+                    ].spacing(4.0).align_x(Alignment::Center).into()
+                };
+                (content, host_col)
+            }
+
+            // ── Group of cards ────────────────────────────────────────────────
+            "group" => {
+                let sep_col = Color { a: 0.14 * opacity, ..fg };
+                let mut cells: Vec<Element<'_, Message>> = Vec::new();
+                for child in &children {
+                    let Some(cell) = self.make_card(child, 1, card_idx, slot, true) else { continue };
+                    if !cells.is_empty() {
+                        cells.push(
+                            container(iced::widget::Space::new())
+                                .width(Length::Fixed(1.0))
+                                .height(Length::Fixed(card_h * 0.55))
+                                .style(move |_: &iced::Theme| iced::widget::container::Style {
+                                    background: Some(Background::Color(sep_col)),
+                                    ..Default::default()
+                                })
+                                .into(),
+                        );
+                    }
+                    cells.push(cell);
+                }
+                if self.rtl() {
+                    cells.reverse();
+                }
+                let strip = iced::widget::Row::from_vec(cells)
+                    .spacing(GROUP_SEP_PAD)
+                    .align_y(Alignment::Center);
+                // A scrollable with no scrollbar is the viewport the drawer
+                // slides behind; children keep their full width inside it.
+                let hidden = iced::widget::scrollable::Scrollbar::new().width(0.0).scroller_width(0.0);
+                // Mirrored, the first child sits at the right end, so the
+                // viewport is anchored there and the drawer opens leftwards.
+                let viewport = iced::widget::scrollable(strip)
+                    .direction(iced::widget::scrollable::Direction::Horizontal(hidden))
+                    .width(Length::Fixed(group_visible_w));
+                let content: Element<'_, Message> = if self.rtl() {
+                    viewport.anchor_right().into()
+                } else {
+                    viewport.into()
+                };
+                (content, Color { a: opacity, ..accent })
+            }
+
             _ => return None,
         };
 
+        // Overlay a small warning glyph in the corner of stale cards
+        let inner: Element<'_, Message> = if stale {
+            let warn_glyph = if nerd { "\u{f071}" } else { "!" };
+            let warn = container(
+                text(warn_glyph)
+                    .size(fsize - 3.0)
+                    .color(Color { r: 0.98, g: 0.70, b: 0.53, a: 0.9 }),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(if self.rtl() { Alignment::Start } else { Alignment::End })
+            .align_y(Alignment::Start);
+            stack![inner, warn].into()
+        } else {
+            inner
+        };
+
+        if bare {
+            return Some(
+                container(inner)
+                    .width(Length::Fixed(card_w))
+                    .align_x(Alignment::Center)
+                    .into(),
+            );
+        }
+
         // ── Card border & glow logic ──────────────────────────────────────────
         //
         // Normal border based on theme, then check for "danger" state to
@@ -1611,13 +3629,13 @@ impl Dashboard {
         // Detect high-value / alert state for accent glow
         let is_alert = match item {
             "cpu"         => self.sys.cpu_pct > 80.0,
-            "memory"      => self.sys.ram_total > 0
-                && (self.sys.ram_used as f32 / self.sys.ram_total as f32) > 0.85,
+            "memory"      => self.sys.ram_frac() > 0.85,
             "temperature" => self.sys.temp_celsius.map(|c| c > 75.0).unwrap_or(false),
-            "battery"     => self.sys.battery_pct
-                .map(|p| p < t.battery_warn_percent && !self.sys.battery_charging)
+            "battery"     => self.sys.battery()
+                .map(|(p, charging)| p < t.battery_warn_percent && !charging)
                 .unwrap_or(false),
             "gpu"         => self.sys.gpu_percent.map(|p| p > 85.0).unwrap_or(false),
+            "disk"        => disk_level(self.sys.disk_frac(), t) != Level::Normal,
             _ => false,
         };
 
@@ -1661,23 +3679,304 @@ impl Dashboard {
                 .into()
         };
 
-        Some(
-            container(final_inner)
-                .width(Length::Fixed(card_w))
-                .height(Length::Fixed(card_h))
-                .align_x(Alignment::Center)
-                .align_y(Alignment::Center)
-                .style(move |_: &iced::Theme| iced::widget::container::Style {
-                    background: Some(Background::Color(card_bg)),
-                    border: Border {
-                        radius: card_radius.into(),
-                        color: border_col,
-                        width: border_w,
-                    },
+        let shadow = match t.shadow {
+            Some(col) => iced::Shadow {
+                color:       Color { a: col.a * opacity, ..col.to_iced() },
+                offset:      iced::Vector::new(0.0, t.shadow_offset),
+                blur_radius: t.shadow_blur,
+            },
+            None => iced::Shadow::default(),
+        };
+
+        let card_elem: Element<'_, Message> = container(final_inner)
+            .width(Length::Fixed(card_w))
+            .height(Length::Fixed(card_h))
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .style(move |_: &iced::Theme| iced::widget::container::Style {
+                background: Some(Background::Color(card_bg)),
+                border: Border {
+                    radius: card_radius.into(),
+                    color: border_col,
+                    width: border_w,
+                },
+                shadow,
+                ..Default::default()
+            })
+            .into();
+
+        // The network card opens the Wi-Fi picker; drawer groups open on click,
+        // and any other card can run a Hyprland dispatcher.
+        let on_press = match item {
+            "network"              => Some(Message::WifiToggle),
+            "caffeine"             => Some(Message::CaffeineToggle),
+            "group" if card.drawer => Some(Message::DrawerToggle(slot)),
+            _ => (!card.dispatch.is_empty()).then(|| Message::HyprDispatch(card.dispatch.clone())),
+        };
+        // Volume and brightness also follow the scroll wheel
+        let scroll_kind = match item {
+            "volume"     => Some("volume"),
+            "brightness" => Some("brightness"),
+            _            => None,
+        };
+        // Right-click picks the caffeine duration
+        let on_right_press = (item == "caffeine").then_some(Message::CaffeineCycle);
+        if on_press.is_none() && on_right_press.is_none() && scroll_kind.is_none() {
+            return Some(card_elem);
+        }
+        let mut area = iced::widget::mouse_area(card_elem);
+        if let Some(msg) = on_press {
+            area = area.on_press(msg).interaction(iced::mouse::Interaction::Pointer);
+        }
+        if let Some(msg) = on_right_press {
+            area = area.on_right_press(msg);
+        }
+        if let Some(kind) = scroll_kind {
+            area = area.on_scroll(move |delta| Message::CardScroll(kind, delta));
+        }
+        Some(area.into())
+    }
+
+    // ── First-run hint and notices ─────────────────────────────────────────────
+
+    fn first_run_panel(&self) -> Element<'_, Message> {
+        let fsize = self.theme.font_size;
+        let fg    = self.theme.foreground.to_iced();
+
+        let path = default_path();
+        let shown = match std::env::var("HOME") {
+            Ok(home) if !home.is_empty() => match path.strip_prefix(&home) {
+                Ok(rest) => format!("~/{}", rest.display()),
+                Err(_)   => path.display().to_string(),
+            },
+            _ => path.display().to_string(),
+        };
+
+        let line = row![
+            text(format!("No config found \u{2014} created {shown}. Run bar-editor to customise it."))
+                .size(fsize - 2.0)
+                .color(Color { a: 0.8, ..fg }),
+            self.panel_link("Open editor", Message::OpenEditor),
+            self.panel_link("\u{d7}", Message::HintDismiss),
+        ]
+        .spacing(10.0)
+        .align_y(Alignment::Center);
+        self.panel_frame(line.into(), Color::from_rgb(0.54, 0.71, 0.98))
+    }
+
+    /// A problem the user should know about that isn't tied to a card.
+    fn notice_panel(&self, notice: &str) -> Element<'_, Message> {
+        let fsize = self.theme.font_size;
+        let fg    = self.theme.foreground.to_iced();
+        let line = row![
+            text(notice.to_string()).size(fsize - 2.0).color(Color { a: 0.8, ..fg }),
+            self.panel_link("\u{d7}", Message::NoticeDismiss),
+        ]
+        .spacing(10.0)
+        .align_y(Alignment::Center);
+        self.panel_frame(line.into(), Color::from_rgb(0.96, 0.54, 0.67))
+    }
+
+    /// Text button for the hint and notice panels.
+    fn panel_link(&self, label: &'static str, msg: Message) -> Element<'_, Message> {
+        let blue = Color::from_rgb(0.54, 0.71, 0.98);
+        iced::widget::button(text(label).size(self.theme.font_size - 2.0).color(blue))
+            .padding([3.0, 8.0])
+            .style(move |_: &iced::Theme, status| {
+                let hov = status == iced::widget::button::Status::Hovered;
+                iced::widget::button::Style {
+                    background: hov.then_some(Background::Color(Color { a: 0.12, ..blue })),
+                    border: Border { radius: 6.0.into(), ..Default::default() },
                     ..Default::default()
-                })
+                }
+            })
+            .on_press(msg)
+            .into()
+    }
+
+    /// Rounded strip under the grid, outlined in `accent`.
+    fn panel_frame<'a>(&self, content: Element<'a, Message>, accent: Color) -> Element<'a, Message> {
+        let bg = self.theme.background.to_iced();
+        container(content)
+            .padding([8.0, 14.0])
+            .style(move |_: &iced::Theme| iced::widget::container::Style {
+                background: Some(Background::Color(Color { a: 0.92, ..bg })),
+                border: Border {
+                    radius: 12.0.into(),
+                    color: Color { a: 0.25, ..accent },
+                    width: 1.0,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    // ── Command palette panel ──────────────────────────────────────────────────
+
+    fn palette_panel(&self) -> Element<'_, Message> {
+        let t     = &self.theme;
+        let fsize = t.font_size;
+        let fg    = t.foreground.to_iced();
+        let bg    = t.background.to_iced();
+        let blue  = Color::from_rgb(0.54, 0.71, 0.98);
+        let dim   = Color { a: 0.55, ..fg };
+        let Some(palette) = &self.palette else {
+            return iced::widget::Space::new().into();
+        };
+
+        let query_line: Element<'_, Message> = if palette.query.is_empty() {
+            text("Search actions\u{2026}").size(fsize + 1.0).color(dim).into()
+        } else {
+            text(format!("{}\u{258f}", palette.query)).size(fsize + 1.0).color(fg).into()
+        };
+        let mut rows: Vec<Element<'_, Message>> = vec![
+            row![text("\u{203a}").size(fsize + 1.0).color(blue), query_line]
+                .spacing(8.0)
+                .align_y(Alignment::Center)
                 .into(),
-        )
+        ];
+
+        let matches = self.palette_matches();
+        if matches.is_empty() {
+            rows.push(text("No matching actions").size(fsize - 2.0).color(dim).into());
+        }
+        let selected = palette.selected.min(matches.len().saturating_sub(1));
+        for (i, item) in matches.into_iter().take(PALETTE_ROWS).enumerate() {
+            let active = i == selected;
+            let hover_col = blue;
+            let btn = iced::widget::button(
+                text(item.label).size(fsize - 1.0).color(if active { blue } else { fg }),
+            )
+            .width(Length::Fill)
+            .padding([4.0, 8.0])
+            .style(move |_: &iced::Theme, status| {
+                let lit = active || status == iced::widget::button::Status::Hovered;
+                iced::widget::button::Style {
+                    background: lit.then_some(Background::Color(Color { a: 0.12, ..hover_col })),
+                    border: Border { radius: 6.0.into(), ..Default::default() },
+                    ..Default::default()
+                }
+            })
+            .on_press(Message::PaletteRun(i));
+            rows.push(btn.into());
+        }
+
+        container(iced::widget::Column::from_vec(rows).spacing(4.0))
+            .width(Length::Fixed(420.0))
+            .padding(14.0)
+            .style(move |_: &iced::Theme| iced::widget::container::Style {
+                background: Some(Background::Color(Color { a: 0.92, ..bg })),
+                border: Border {
+                    radius: 12.0.into(),
+                    color: Color { a: 0.25, ..blue },
+                    width: 1.0,
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+
+    // ── Wi-Fi picker panel ─────────────────────────────────────────────────────
+
+    fn wifi_panel(&self) -> Element<'_, Message> {
+        let t     = &self.theme;
+        let fsize = t.font_size;
+        let fg    = t.foreground.to_iced();
+        let nerd  = t.use_nerd_icons;
+        let blue  = Color::from_rgb(0.54, 0.71, 0.98);
+        let green = Color::from_rgb(0.67, 0.88, 0.63);
+        let dim   = Color { a: 0.55, ..fg };
+
+        let mut rows: Vec<Element<'_, Message>> = vec![
+            text("Wi-Fi networks").size(fsize - 1.0).color(Color { a: 0.7, ..fg }).into(),
+        ];
+
+        if self.wifi_busy && self.wifi_networks.is_empty() {
+            rows.push(text("Scanning\u{2026}").size(fsize - 2.0).color(dim).into());
+        } else if self.wifi_networks.is_empty() {
+            rows.push(text("No networks found").size(fsize - 2.0).color(dim).into());
+        }
+
+        for net in self.wifi_networks.iter().take(8) {
+            let level = signal_level(net.signal);
+            let bars = match (level, nerd) {
+                (3, true)  => "\u{f0928}",
+                (2, true)  => "\u{f0925}",
+                (1, true)  => "\u{f0922}",
+                (_, true)  => "\u{f091f}",
+                (3, false) => "\u{2582}\u{2584}\u{2586}\u{2588}",
+                (2, false) => "\u{2582}\u{2584}\u{2586} ",
+                (1, false) => "\u{2582}\u{2584}  ",
+                (_, false) => "\u{2582}   ",
+            };
+            // Good signal in green, weak in the theme's warning colour,
+            // barely usable in its critical colour.
+            let bars_col = match level {
+                2.. => green,
+                1   => t.warning.to_iced(),
+                _   => t.critical.to_iced(),
+            };
+            let lock = match (net.secured, nerd) {
+                (true, true)  => "\u{f033e}",
+                (true, false) => "*",
+                (false, _)    => "",
+            };
+            // Secured networks without a saved connection need a password,
+            // which the dashboard doesn't prompt for.
+            let needs_setup = net.secured && !net.known && !net.active;
+            let name_col = if net.active { blue } else { fg };
+
+            let mut line = row![
+                text(bars).size(fsize).color(bars_col),
+                text(net.ssid.clone()).size(fsize - 1.0).color(name_col).width(Length::Fill),
+                text(lock).size(fsize - 2.0).color(dim),
+            ]
+            .spacing(10.0)
+            .align_y(Alignment::Center);
+            if needs_setup {
+                line = line.push(text("configure externally").size(fsize - 4.0).color(dim));
+            }
+
+            let hover_col = blue;
+            let mut btn = iced::widget::button(line)
+                .width(Length::Fill)
+                .padding([4.0, 8.0])
+                .style(move |_: &iced::Theme, status| {
+                    let hov = status == iced::widget::button::Status::Hovered;
+                    iced::widget::button::Style {
+                        background: hov.then_some(Background::Color(Color { a: 0.12, ..hover_col })),
+                        border: Border { radius: 6.0.into(), ..Default::default() },
+                        ..Default::default()
+                    }
+                });
+            if !needs_setup && !net.active && !self.wifi_busy {
+                btn = btn.on_press(Message::WifiConnect(net.ssid.clone()));
+            }
+            rows.push(btn.into());
+        }
+
+        if let Some(status) = &self.wifi_status {
+            let (msg, col) = match status {
+                Ok(m)  => (m.clone(), Color::from_rgb(0.65, 0.89, 0.63)),
+                Err(e) => (e.clone(), Color::from_rgb(0.96, 0.54, 0.67)),
+            };
+            rows.push(text(msg).size(fsize - 3.0).color(col).into());
+        }
+
+        let bg = t.background.to_iced();
+        container(iced::widget::Column::from_vec(rows).spacing(4.0))
+            .width(Length::Fixed(420.0))
+            .padding(14.0)
+            .style(move |_: &iced::Theme| iced::widget::container::Style {
+                background: Some(Background::Color(Color { a: 0.92, ..bg })),
+                border: Border {
+                    radius: 12.0.into(),
+                    color: Color { a: 0.25, ..blue },
+                    width: 1.0,
+                },
+                ..Default::default()
+            })
+            .into()
     }
 
     // ── Mini progress bar (used by full/vivid themes) ──────────────────────────
@@ -1716,14 +4015,43 @@ impl Dashboard {
     // ── Subscriptions ─────────────────────────────────────────────────────────
 
     fn subscription(&self) -> Subscription<Message> {
-        // Always run at 60fps while intro animation is playing, or when media
-        // is playing (for equalizer animation). Otherwise step down to 1fps.
-        let tick_ms = if self.intro_t < 1.0 || self.sys.media_playing { 16 } else { 1000 };
+        // Always run at 60fps while the intro or a drawer is animating, or when
+        // media is playing (for equalizer animation). Otherwise step down to 1fps.
+        // With every output off there's nobody to animate for, so no tick.
+        let animating = self.intro_t < 1.0 || self.drawers.values().any(Drawer::animating);
+        let eq_playing = self.sys.media_playing && self.theme.animate();
+        let tick_ms = if animating || eq_playing { 16 } else { 1000 };
+        let tick = if self.outputs_asleep {
+            Subscription::none()
+        } else {
+            iced::time::every(Duration::from_millis(tick_ms)).map(|_| Message::AnimFrame)
+        };
+        // Slow sources stop while the outputs are off; resubscribing on wake
+        // reads each of them again straight away.
+        let slow = if self.outputs_asleep {
+            Subscription::none()
+        } else {
+            Subscription::batch(
+                self.read_opts.slow_sources().into_iter().map(|source| Subscription::run_with(source, slow_stream)),
+            )
+        };
+        let peripherals = if self.read_opts.peripherals {
+            Subscription::run(peripherals_stream)
+        } else {
+            Subscription::none()
+        };
+        let metrics = match self.metrics_listen {
+            Some(addr) => Subscription::run_with(addr, metrics_server),
+            None       => Subscription::none(),
+        };
         Subscription::batch([
             iced::keyboard::listen().map(Message::KeyEvent),
-            Subscription::run(sys_stream),
-            iced::time::every(Duration::from_millis(tick_ms))
-                .map(|_| Message::AnimFrame),
+            Subscription::run_with(self.read_opts.clone(), sys_stream),
+            slow,
+            peripherals,
+            Subscription::run(config_stream),
+            tick,
+            metrics,
         ])
     }
 
@@ -1737,32 +4065,478 @@ impl Dashboard {
 
 // ── Live update stream ────────────────────────────────────────────────────────
 
-fn sys_stream() -> impl iced::futures::Stream<Item = Message> {
+fn sys_stream(opts: &ReadOptions) -> impl iced::futures::Stream<Item = Message> {
+    let opts = opts.clone();
     iced::stream::channel(4, |mut sender: Sender<Message>| async move {
-        // Load weather_location once at stream startup
-        let weather_location = {
-            let config = load_config(default_path()).unwrap_or_default();
-            config.weather_location
+        // Output power, asked of Hyprland's `monitors` request: every
+        // DPMS_CHECK_INTERVAL while an output is on, every poll while all are
+        // off so waking is noticed at once.
+        let mut dpms       = Ticker::new(DPMS_CHECK_INTERVAL);
+        let mut dpms_retry = Backoff::new(DPMS_CHECK_INTERVAL, DPMS_MAX_BACKOFF);
+
+        let mut asleep    = false;
+        let mut last_read = None::<Instant>;
+
+        loop {
+            // While every output is off, read only every ASLEEP_POLL_INTERVAL;
+            // on wake, read everything at once so no card shows night-old data.
+            if dpms.due(Instant::now()) {
+                match tokio::task::spawn_blocking(outputs_awake).await.ok().flatten() {
+                    Some(awake) => {
+                        dpms_retry.reset();
+                        dpms.every = if awake { DPMS_CHECK_INTERVAL } else { SYS_POLL_INTERVAL };
+                        if awake == asleep {
+                            asleep = !awake;
+                            let _ = sender.try_send(Message::OutputsPower(awake));
+                            if awake {
+                                last_read = None;
+                            }
+                        }
+                    }
+                    // No answer says nothing about the outputs: keep the
+                    // current state, and ask less and less often.
+                    None => dpms.every = dpms_retry.next(),
+                }
+            }
+            let now = Instant::now();
+            if asleep && last_read.is_some_and(|at| now.duration_since(at) < ASLEEP_POLL_INTERVAL) {
+                tokio::time::sleep(SYS_POLL_INTERVAL).await;
+                continue;
+            }
+            last_read = Some(now);
+
+            // Read in its own task so a panic in one read (e.g. inside sysinfo)
+            // is logged and retried on the next tick instead of ending the stream.
+            match tokio::spawn(read_sys_snapshot(opts.clone(), asleep)).await {
+                Ok(snap) => {
+                    let _ = sender.try_send(Message::SysReady(snap));
+                }
+                Err(e) => tracing::error!("System snapshot read failed: {e}"),
+            }
+            tokio::time::sleep(SYS_POLL_INTERVAL).await;
+        }
+    })
+}
+
+/// Read one slow source now and then every `every`, for as long as the
+/// subscription lives.  A failed read keeps the last value on screen.
+fn slow_stream((source, every): &(SlowSource, Duration)) -> impl iced::futures::Stream<Item = Message> {
+    let (source, every) = (source.clone(), *every);
+    iced::stream::channel(1, move |mut sender: Sender<Message>| async move {
+        loop {
+            if let Some(slice) = source.read().await {
+                // Waits for room rather than dropping: the next reading may
+                // be half an hour away.
+                if sender.send(Message::SliceReady(slice)).await.is_err() {
+                    return;
+                }
+            }
+            tokio::time::sleep(every).await;
+        }
+    })
+}
+
+/// The peripherals card's device list, kept current from UPower's signals.
+/// Restarts `upower --monitor-detail` with backoff when it exits or can't
+/// start (no UPower daemon, upower not installed).
+fn peripherals_stream() -> impl iced::futures::Stream<Item = Message> {
+    iced::stream::channel(1, |mut sender: Sender<Message>| async move {
+        let mut retry = Backoff::new(Duration::from_secs(5), Duration::from_secs(300));
+        loop {
+            match watch_peripherals(&mut sender, &mut retry).await {
+                Ok(()) => tracing::debug!("upower --monitor-detail exited"),
+                Err(e) => tracing::debug!("upower --monitor-detail: {e}"),
+            }
+            if sender.is_closed() {
+                return;
+            }
+            tokio::time::sleep(retry.next()).await;
+        }
+    })
+}
+
+/// Run each lifecycle hook through `sh -c` in the background.  Nothing waits
+/// on them; a failure to start or a non-zero exit is only logged.
+fn run_hooks(hook: &'static str, commands: &[String]) -> Task<Message> {
+    Task::batch(commands.iter().cloned().map(|cmd| {
+        Task::future(async move {
+            match tokio::process::Command::new("sh").args(["-c", &cmd]).status().await {
+                Ok(status) if status.success() => {}
+                Ok(status) => tracing::warn!("{hook} hook `{cmd}` failed: {status}"),
+                Err(e)     => tracing::warn!("{hook} hook `{cmd}` could not start: {e}"),
+            }
+        })
+        .discard()
+    }))
+}
+
+/// Run a Hyprland dispatcher and log the outcome.  Blocking.
+fn run_dispatch(args: &str) {
+    match bar_core::hypr::dispatch(args) {
+        Ok(_)  => tracing::info!("dispatch {args}: ok"),
+        Err(e) => tracing::warn!("{e}"),
+    }
+}
+
+// ── Metrics endpoint ──────────────────────────────────────────────────────────
+
+/// Latest `/metrics` body, published after each snapshot.
+static EXPORTED_METRICS: OnceLock<tokio::sync::watch::Sender<String>> = OnceLock::new();
+
+fn exported_metrics() -> &'static tokio::sync::watch::Sender<String> {
+    EXPORTED_METRICS.get_or_init(|| tokio::sync::watch::channel(String::new()).0)
+}
+
+/// Serve `/metrics` on `addr` until the subscription is dropped (the address
+/// changes or is cleared).  Only yields a `Notice` if the address can't be bound.
+fn metrics_server(addr: &SocketAddr) -> impl iced::futures::Stream<Item = Message> {
+    let addr = *addr;
+    iced::stream::channel(1, move |mut sender: Sender<Message>| async move {
+        let listener = match tokio::net::TcpListener::bind(addr).await {
+            Ok(l) => l,
+            Err(e) => {
+                tracing::warn!("Cannot serve metrics on {addr}: {e}");
+                let _ = sender.send(Message::Notice(format!("Cannot serve metrics on {addr}: {e}"))).await;
+                return;
+            }
         };
+        tracing::info!("Serving Prometheus metrics on http://{addr}/metrics");
         loop {
-            tokio::time::sleep(Duration::from_secs(2)).await;
-            let snap = read_sys_snapshot(weather_location.clone()).await;
-            let _ = sender.try_send(Message::SysReady(snap));
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_metrics(stream));
+                }
+                Err(e) => tracing::warn!("Metrics endpoint accept failed: {e}"),
+            }
         }
     })
 }
 
+/// Answer one HTTP request: the metrics for `GET /metrics`, 404 otherwise.
+async fn serve_metrics(mut stream: tokio::net::TcpStream) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut buf = [0u8; 1024];
+    let n = match tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await {
+        Ok(Ok(n)) => n,
+        _ => return,
+    };
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut parts = request.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", exported_metrics().borrow().clone()),
+        _                               => ("404 Not Found", "not found\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len(),
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+/// Parse `metrics_listen`; empty or invalid (logged) disables the endpoint.
+fn parse_listen(addr: &str) -> Option<SocketAddr> {
+    if addr.is_empty() {
+        return None;
+    }
+    addr.parse()
+        .map_err(|e| tracing::warn!("Ignoring metrics_listen '{addr}': {e}"))
+        .ok()
+}
+
+/// Escape a Prometheus label value.
+fn prom_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Whether any output is powered on, from the `dpmsStatus` lines of
+/// Hyprland's monitor list.  `None` when that can't be told (not running
+/// under Hyprland, socket error).
+fn outputs_awake() -> Option<bool> {
+    let reply = bar_core::hypr::request("monitors").ok()?;
+    let mut statuses = reply
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("dpmsStatus:"))
+        .peekable();
+    statuses.peek()?;
+    Some(statuses.any(|s| s.trim() == "1"))
+}
+
+/// Watch the config file and everything it includes, emitting
+/// `ConfigReloaded` after each change.  The watcher is rebuilt after every
+/// reload since the set of included files may have changed.
+fn config_stream() -> impl iced::futures::Stream<Item = Message> {
+    iced::stream::channel(4, |mut sender: Sender<Message>| async move {
+        let path = default_path();
+        let mut current = startup_config().clone();
+        let mut sources = load_with_sources(&path)
+            .map(|(_, sources)| sources)
+            .unwrap_or_else(|_| vec![path.clone()]);
+        loop {
+            let (_watcher, mut rx) = ConfigWatcher::spawn(&sources);
+            let Some(changed) = rx.recv().await else { return };
+            // One save often arrives as a burst of write/create events;
+            // wait for it to settle so the burst becomes a single reload.
+            while let Ok(Some(_)) = tokio::time::timeout(CONFIG_DEBOUNCE, rx.recv()).await {}
+            match load_with_sources(&path) {
+                Ok((config, new_sources)) => {
+                    sources = new_sources;
+                    // Saves that don't change any value (touch, whitespace,
+                    // comments) aren't worth a reload.
+                    if config == current {
+                        tracing::debug!("{} changed but the config didn't", changed.display());
+                        continue;
+                    }
+                    tracing::info!("{} changed; reloading config", changed.display());
+                    current = config.clone();
+                    let _ = sender.try_send(Message::ConfigReloaded(Box::new(config)));
+                }
+                Err(e) => tracing::warn!("Config reload failed, keeping current config: {e}"),
+            }
+        }
+    })
+}
+
+// ── Card labels ───────────────────────────────────────────────────────────────
+//
+// Icon and value text for the simple cards, kept apart from the widgets so
+// what a card says depends only on the snapshot and a few theme flags.
+
+/// Interface name, or "No network".  `signal` (`"pct"` or `"bars"`) appends
+/// the Wi-Fi signal when the interface has one.
+fn network_label(snap: &DashSnapshot, nerd: bool, signal: &str) -> (&'static str, String) {
+    let icon = if nerd { "\u{f05a9}" } else { "NET" };
+    let Some((iface, signal_pct)) = snap.network() else {
+        return (icon, "No network".to_string());
+    };
+    let suffix = match (signal, signal_pct) {
+        ("pct", Some(pct))  => format!(" \u{00b7} {pct}%"),
+        ("bars", Some(pct)) => {
+            let bars = match signal_level(pct) {
+                3 => "\u{2582}\u{2584}\u{2586}\u{2588}",
+                2 => "\u{2582}\u{2584}\u{2586}",
+                1 => "\u{2582}\u{2584}",
+                _ => "\u{2582}",
+            };
+            format!(" {bars}")
+        }
+        _ => String::new(),
+    };
+    (icon, format!("{iface}{suffix}"))
+}
+
+/// Charge percentage, with a bolt icon while charging.  `None` without a battery.
+fn battery_label(snap: &DashSnapshot, nerd: bool, pad: bool) -> Option<(&'static str, String)> {
+    let (pct, charging) = snap.battery()?;
+    let icon = match (charging, nerd) {
+        (true, true)   => "\u{f0e7}",
+        (true, false)  => "⚡",
+        (false, true)  => "\u{f0079}",
+        (false, false) => "BAT",
+    };
+    Some((icon, fmt_pct(pct as f32, pad)))
+}
+
+fn cpu_label(snap: &DashSnapshot, nerd: bool, pad: bool) -> (&'static str, String) {
+    let icon = if nerd { "\u{f4bc}" } else { "CPU" };
+    (icon, fmt_pct(snap.cpu_pct, pad))
+}
+
+/// Used memory, and the `/ total` line under it.
+/// `size` formats a byte count (`Dashboard::fmt_size`).
+fn memory_label(snap: &DashSnapshot, nerd: bool, size: impl Fn(u64) -> String) -> (&'static str, String, String) {
+    let icon = if nerd { "\u{f035b}" } else { "RAM" };
+    (icon, size(snap.ram_used), format!("/ {}", size(snap.ram_total)))
+}
+
+fn swap_label(snap: &DashSnapshot, nerd: bool, size: impl Fn(u64) -> String) -> (&'static str, String) {
+    let icon = if nerd { "\u{f0552}" } else { "SWP" };
+    (icon, format!("{} / {}", size(snap.swap_used), size(snap.swap_total)))
+}
+
+fn uptime_label(snap: &DashSnapshot, nerd: bool) -> (&'static str, String) {
+    let icon = if nerd { "\u{f150e}" } else { "UP" };
+    (icon, fmt_uptime(snap.uptime_secs))
+}
+
+/// Whole degrees in `unit`.  `None` when no sensor was found.
+fn temperature_label(snap: &DashSnapshot, nerd: bool, unit: &str) -> Option<(&'static str, String)> {
+    let temp = snap.temp_celsius?;
+    let icon = if nerd { "\u{f050f}" } else { "TMP" };
+    Some((icon, fmt_temp(temp, unit)))
+}
+
+/// A Celsius reading in the configured `temperature_unit`.
+fn degrees(celsius: f32, unit: &str) -> f32 {
+    if unit == "f" { celsius * 9.0 / 5.0 + 32.0 } else { celsius }
+}
+
+/// `47°C` or `117°F`.
+fn fmt_temp(celsius: f32, unit: &str) -> String {
+    let suffix = if unit == "f" { 'F' } else { 'C' };
+    format!("{:.0}°{suffix}", degrees(celsius, unit))
+}
+
+fn peripheral_icon(kind: &str, nerd: bool) -> &'static str {
+    match (kind, nerd) {
+        ("mouse", true)                   => "\u{f037d}",
+        ("keyboard", true)                => "\u{f030c}",
+        ("headset" | "headphones", true)  => "\u{f02cb}",
+        (_, true)                         => "\u{f0297}",
+        ("mouse", false)                  => "MSE",
+        ("keyboard", false)               => "KBD",
+        ("headset" | "headphones", false) => "HP",
+        (_, false)                        => "PAD",
+    }
+}
+
+fn updates_label(snap: &DashSnapshot, nerd: bool) -> (&'static str, String) {
+    let icon = if nerd { "\u{f0954}" } else { "UPD" };
+    let val  = match snap.update_count {
+        Some(0) => "Up to date".to_string(),
+        Some(n) => format!("{n} updates"),
+        None    => "Checking\u{2026}".to_string(),
+    };
+    (icon, val)
+}
+
+/// Time and date lines of the clock card, in the theme's `clock_format` and
+/// `date_format`.
+fn clock_label<Tz: chrono::TimeZone>(
+    now:         &chrono::DateTime<Tz>,
+    time_format: &str,
+    date_format: &str,
+) -> (String, String)
+where
+    Tz::Offset: std::fmt::Display,
+{
+    (now.format(time_format).to_string(), now.format(date_format).to_string())
+}
+
+/// Icon, caption and level.  With `show_port` the icon and caption follow
+/// the output port (headphones, HDMI, a Bluetooth sink) when it's known.
+fn volume_label(snap: &DashSnapshot, nerd: bool, show_port: bool, pad: bool) -> (&'static str, &str, String) {
+    let port = snap.audio_port.as_ref().filter(|_| show_port);
+    let icon = if snap.volume_muted {
+        if nerd { "\u{f075f}" } else { "M" }
+    } else if let Some(port) = port {
+        port.icon(nerd)
+    } else if nerd { "\u{f057e}" } else { "V" };
+    let caption = port.map(AudioPort::label).unwrap_or("Volume");
+    (icon, caption, fmt_pct(snap.volume.unwrap_or(0.0) * 100.0, pad))
+}
+
+fn brightness_label(snap: &DashSnapshot, nerd: bool, pad: bool) -> (&'static str, String) {
+    let icon = if nerd { "\u{f00e0}" } else { "BRT" };
+    (icon, fmt_pct(snap.brightness.unwrap_or(50) as f32, pad))
+}
+
+/// Used space on `/`, and the `/ total` line under it.
+fn disk_label(snap: &DashSnapshot, nerd: bool, size: impl Fn(u64) -> String) -> (&'static str, String, String) {
+    let icon = if nerd { "\u{f01bc}" } else { "DSK" };
+    (icon, size(snap.disk_used), format!("/ {}", size(snap.disk_total)))
+}
+
+/// The 1, 5 and 15 minute load averages.  `num` localises the decimal
+/// separator (`Dashboard::fmt_num`).
+fn load_label(snap: &DashSnapshot, nerd: bool, num: impl Fn(String) -> String) -> (&'static str, [String; 3]) {
+    let icon = if nerd { "\u{f080}" } else { "LOAD" };
+    let avg  = |load: f32| num(format!("{load:.2}"));
+    (icon, [avg(snap.load_1), avg(snap.load_5), avg(snap.load_15)])
+}
+
+/// Utilisation, temperature and `used / total` memory; the last two are
+/// empty when the driver doesn't report them.  `None` without a GPU.
+fn gpu_label(
+    snap: &DashSnapshot,
+    nerd: bool,
+    pad:  bool,
+    unit: &str,
+    size: impl Fn(u64) -> String,
+) -> Option<(&'static str, String, String, String)> {
+    let pct  = snap.gpu_percent?;
+    let icon = if nerd { "\u{f071b}" } else { "GPU" };
+    let temp = snap.gpu_temp.map(|c| fmt_temp(c, unit)).unwrap_or_default();
+    let mem  = match (snap.gpu_mem_used, snap.gpu_mem_total) {
+        (Some(used), Some(total)) if total > 0 => format!("{} / {}", size(used), size(total)),
+        _ => String::new(),
+    };
+    Some((icon, fmt_pct(pct, pad), temp, mem))
+}
+
+/// Connection status, and the connected device's name cut to `max_chars`.
+fn bluetooth_label(snap: &DashSnapshot, nerd: bool, max_chars: usize) -> (&'static str, &'static str, String) {
+    let icon = if nerd { "\u{f00af}" } else { "BT" };
+    if !snap.bt_connected {
+        return (icon, "Disconnected", String::new());
+    }
+    let device = snap.bt_device_name.as_deref().unwrap_or("Connected");
+    (icon, "Connected", truncate_smart(device, max_chars, "\u{2026}"))
+}
+
+/// Previous, play/pause and next icons, then the title cut to `max_chars`
+/// and the artist.
+fn media_label(snap: &DashSnapshot, nerd: bool, max_chars: usize) -> ([&'static str; 3], String, String) {
+    let icons = match (nerd, snap.media_playing) {
+        (true, true)   => ["\u{f0602}", "\u{f03e4}", "\u{f0604}"],
+        (true, false)  => ["\u{f0602}", "\u{f040a}", "\u{f0604}"],
+        (false, true)  => ["⏮", "⏸", "⏭"],
+        (false, false) => ["⏮", "▶", "⏭"],
+    };
+    let title  = snap.media_title.as_deref().unwrap_or("Nothing playing");
+    let artist = snap.media_artist.clone().unwrap_or_default();
+    (icons, truncate_smart(title, max_chars, "\u{2026}"), artist)
+}
+
+/// wttr.in's `City: ⛅️  +12°C` split into the city and the conditions.
+fn weather_label(snap: &DashSnapshot, nerd: bool) -> (&'static str, String, String) {
+    let icon = if nerd { "\u{f0599}" } else { "WX" };
+    let raw  = &snap.weather_text;
+    if raw.is_empty() {
+        return (icon, "Fetching\u{2026}".to_string(), String::new());
+    }
+    match raw.split_once(':') {
+        Some((city, detail)) => (icon, city.trim().to_string(), detail.trim().to_string()),
+        None                 => (icon, raw.clone(), String::new()),
+    }
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
-fn fmt_bytes(bytes: u64) -> String {
-    if bytes >= 1_073_741_824 {
-        format!("{:.1}G", bytes as f64 / 1_073_741_824.0)
-    } else if bytes >= 1_048_576 {
-        format!("{:.0}M", bytes as f64 / 1_048_576.0)
-    } else if bytes >= 1_024 {
-        format!("{:.0}K", bytes as f64 / 1024.0)
+/// Format a percentage, right-aligned to three digits when `pad` is set so
+/// the label keeps its width as the value crosses 10% or 100%.
+fn fmt_pct(value: f32, pad: bool) -> String {
+    let digits = format!("{value:.0}");
+    if !pad {
+        return format!("{digits}%");
+    }
+    // Pad with figure spaces: they are as wide as a digit even in fonts with
+    // proportional spaces, where ASCII padding would still jitter.
+    let fill = "\u{2007}".repeat(3usize.saturating_sub(digits.chars().count()));
+    format!("{fill}{digits}%")
+}
+
+/// Where a reading sits against its configured thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Disk usage as a fraction (0–1) against `disk_warn_percent` and
+/// `disk_critical_percent`.
+fn disk_level(frac: f32, t: &Theme) -> Level {
+    let pct = frac * 100.0;
+    if pct >= t.disk_critical_percent as f32 {
+        Level::Critical
+    } else if pct >= t.disk_warn_percent as f32 {
+        Level::Warning
     } else {
-        format!("{bytes}B")
+        Level::Normal
     }
 }
 
@@ -1772,6 +4546,22 @@ fn fmt_uptime(secs: u64) -> String {
     if h > 0 { format!("{h}h {m:02}m") } else { format!("{m}m") }
 }
 
+/// Time left on a countdown: `mm:ss`, or `h:mm:ss` from an hour up.
+fn fmt_countdown(left: Duration) -> String {
+    let secs = left.as_secs();
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 { format!("{h}:{m:02}:{s:02}") } else { format!("{m:02}:{s:02}") }
+}
+
+/// A duration in whole minutes: `30m`, `2h`, `1h30m`.
+fn fmt_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h{m:02}m"),
+    }
+}
+
 /// How many grid columns this card type spans (1 = normal, 2 = wide).
 fn card_span(item: &str) -> usize {
     match item {
@@ -1780,11 +4570,727 @@ fn card_span(item: &str) -> usize {
     }
 }
 
+/// Drawer state after a reload: each drawer follows its group card, staying
+/// at the same position when the card is still there, and is dropped when
+/// the card was removed or changed.
+fn carry_drawers(
+    old:     &[CardConfig],
+    new:     &[CardConfig],
+    drawers: HashMap<usize, Drawer>,
+) -> HashMap<usize, Drawer> {
+    drawers
+        .into_iter()
+        .filter_map(|(slot, drawer)| {
+            let card = old.get(slot)?;
+            if new.get(slot) == Some(card) {
+                return Some((slot, drawer));
+            }
+            new.iter().position(|c| c == card).map(|moved| (moved, drawer))
+        })
+        .collect()
+}
+
+/// The caffeine preset after `caffeine_minutes` is reloaded: the same
+/// duration if it's still offered, else the first.
+fn carry_preset(old: &[u32], new: &[u32], preset: usize) -> usize {
+    old.get(preset)
+        .and_then(|minutes| new.iter().position(|m| m == minutes))
+        .unwrap_or(0)
+}
+
+/// Columns a grid item spans: its `col_span` if above 1, one per enabled
+/// child for a group, else the card type's default — never more than `cols`.
+fn item_span(item: &CardConfig, cols: usize) -> usize {
+    if item.col_span > 1 {
+        (item.col_span as usize).min(cols)
+    } else if item.kind == "group" {
+        item.children.iter().filter(|c| c.enabled).count().clamp(1, cols)
+    } else {
+        card_span(&item.kind).min(cols)
+    }
+}
+
+/// One row of the grid, its cards already in display order.
+fn grid_row(cards: Vec<Element<'_, Message>>, gap: f32) -> Element<'_, Message> {
+    iced::widget::Row::from_vec(cards).spacing(gap).align_y(Alignment::Center).into()
+}
+
+/// Card height relative to the theme's base height.
+fn card_height(item: &str) -> f32 {
+    match item {
+        "clock" | "media" => 1.25,
+        "load"            => 1.10,
+        _                 => 1.0,
+    }
+}
+
 fn lerp_color(a: Color, b: Color, t: f32) -> Color {
-    Color {
-        r: a.r + (b.r - a.r) * t,
-        g: a.g + (b.g - a.g) * t,
-        b: a.b + (b.b - a.b) * t,
-        a: a.a + (b.a - a.a) * t,
+    ThemeColor::from(a).mix(ThemeColor::from(b), t).to_iced()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    // ── Named fixtures for the card label table ──────────────────────────────
+
+    const GIB: u64 = 1_073_741_824;
+
+    fn idle_laptop() -> DashSnapshot {
+        DashSnapshot {
+            cpu_pct:        3.2,
+            ram_used:       2 * GIB,
+            ram_total:      8 * GIB,
+            net_iface:      "wlan0".to_string(),
+            net_signal_pct: Some(78),
+            volume:         Some(0.35),
+            audio_port:     Some(AudioPort::Speakers),
+            brightness:     Some(40),
+            battery_pct:    Some(64),
+            uptime_secs:    2 * 3600 + 13 * 60,
+            temp_celsius:   Some(41.0),
+            update_count:   Some(0),
+            disk_used:      120 * GIB,
+            disk_total:     256 * GIB,
+            load_1:         0.12,
+            load_5:         0.20,
+            load_15:        0.18,
+            media_title:    Some("Lo-fi Beats".to_string()),
+            media_artist:   Some("ChilledCow".to_string()),
+            weather_text:   "Berlin: ⛅ +12°C".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn loaded_desktop() -> DashSnapshot {
+        DashSnapshot {
+            cpu_pct:        97.6,
+            ram_used:       28 * GIB,
+            ram_total:      32 * GIB,
+            swap_used:      3 * GIB,
+            swap_total:     8 * GIB,
+            net_iface:      "eth0".to_string(),
+            volume:         Some(1.25),
+            audio_port:     Some(AudioPort::Headphones),
+            uptime_secs:    148 * 3600 + 2 * 60,
+            temp_celsius:   Some(88.6),
+            update_count:   Some(42),
+            disk_used:      1900 * GIB,
+            disk_total:     2048 * GIB,
+            load_1:         15.87,
+            load_5:         12.40,
+            load_15:        9.05,
+            gpu_percent:    Some(99.4),
+            gpu_temp:       Some(83.2),
+            gpu_mem_used:   Some(10 * GIB),
+            gpu_mem_total:  Some(12 * GIB),
+            bt_connected:   true,
+            bt_device_name: Some("WH-1000XM4 Headphones".to_string()),
+            media_title:    Some("Bohemian Rhapsody (Remastered 2011)".to_string()),
+            media_artist:   Some("Queen".to_string()),
+            media_playing:  true,
+            weather_text:   "Oslo: 🌧 +4°C".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn no_battery() -> DashSnapshot {
+        DashSnapshot {
+            cpu_pct:      12.0,
+            ram_used:     4 * GIB,
+            ram_total:    16 * GIB,
+            net_iface:    "enp3s0".to_string(),
+            volume:       Some(0.6),
+            volume_muted: true,
+            uptime_secs:  45 * 60,
+            update_count: Some(3),
+            disk_used:    40 * GIB,
+            disk_total:   500 * GIB,
+            load_1:       0.5,
+            load_5:       0.4,
+            load_15:      0.3,
+            gpu_percent:  Some(4.0),
+            bt_connected: true,
+            weather_text: "Partly cloudy +12°C".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn offline() -> DashSnapshot {
+        DashSnapshot {
+            cpu_pct:     1.0,
+            ram_used:    GIB,
+            ram_total:   4 * GIB,
+            brightness:  Some(75),
+            battery_pct: Some(22),
+            uptime_secs: 5 * 60,
+            disk_used:   512 * 1_048_576,
+            disk_total:  32 * GIB,
+            ..Default::default()
+        }
+    }
+
+    fn charging_full() -> DashSnapshot {
+        DashSnapshot {
+            cpu_pct:          18.5,
+            ram_used:         6 * GIB,
+            ram_total:        16 * GIB,
+            swap_used:        512 * 1_048_576,
+            swap_total:       4 * GIB,
+            net_iface:        "wlan0".to_string(),
+            net_signal_pct:   Some(100),
+            volume:           Some(0.5),
+            audio_port:       Some(AudioPort::Bluetooth("Pixel Buds".to_string())),
+            brightness:       Some(100),
+            battery_pct:      Some(100),
+            battery_charging: true,
+            uptime_secs:      26 * 60,
+            temp_celsius:     Some(35.0),
+            update_count:     Some(1),
+            disk_used:        200 * GIB,
+            disk_total:       512 * GIB,
+            load_1:           1.0,
+            load_5:           0.75,
+            load_15:          0.5,
+            bt_connected:     true,
+            bt_device_name:   Some("Pixel Buds".to_string()),
+            media_title:      Some("Podcast".to_string()),
+            media_playing:    true,
+            ..Default::default()
+        }
+    }
+
+    /// Every card's label for `snap`, one line per card, as `kind: parts`
+    /// with empty parts left out and hidden cards shown as `kind: -`.
+    fn card_labels(snap: &DashSnapshot, nerd: bool) -> Vec<String> {
+        fn line(kind: &str, parts: &[&str]) -> String {
+            let parts: Vec<&str> = parts.iter().copied().filter(|p| !p.is_empty()).collect();
+            format!("{kind}: {}", parts.join(" | "))
+        }
+        let hidden = |kind: &str| format!("{kind}: -");
+
+        let (icon, val) = network_label(snap, nerd, "pct");
+        let mut lines = vec![line("network", &[icon, &val])];
+        lines.push(match battery_label(snap, nerd, false) {
+            Some((icon, val)) => line("battery", &[icon, &val]),
+            None              => hidden("battery"),
+        });
+        let (icon, val) = cpu_label(snap, nerd, false);
+        lines.push(line("cpu", &[icon, &val]));
+        let (icon, val, sub) = memory_label(snap, nerd, size);
+        lines.push(line("memory", &[icon, &val, &sub]));
+        let (icon, val) = swap_label(snap, nerd, size);
+        lines.push(line("swap", &[icon, &val]));
+        let (icon, [l1, l5, l15]) = load_label(snap, nerd, |s| s);
+        lines.push(line("load", &[icon, &l1, &l5, &l15]));
+        lines.push(match gpu_label(snap, nerd, false, "c", size) {
+            Some((icon, pct, temp, mem)) => line("gpu", &[icon, &pct, &temp, &mem]),
+            None                         => hidden("gpu"),
+        });
+        let (icon, val, sub) = disk_label(snap, nerd, size);
+        lines.push(line("disk", &[icon, &val, &sub]));
+        let (icon, caption, val) = volume_label(snap, nerd, true, false);
+        lines.push(line("volume", &[icon, caption, &val]));
+        let (icon, val) = brightness_label(snap, nerd, false);
+        lines.push(line("brightness", &[icon, &val]));
+        let ([prev, play, next], title, artist) = media_label(snap, nerd, 20);
+        lines.push(line("media", &[prev, play, next, &title, &artist]));
+        let (icon, val) = uptime_label(snap, nerd);
+        lines.push(line("uptime", &[icon, &val]));
+        lines.push(match temperature_label(snap, nerd, "c") {
+            Some((icon, val)) => line("temperature", &[icon, &val]),
+            None              => hidden("temperature"),
+        });
+        let (icon, val) = updates_label(snap, nerd);
+        lines.push(line("updates", &[icon, &val]));
+        let (icon, status, device) = bluetooth_label(snap, nerd, 16);
+        lines.push(line("bluetooth", &[icon, status, &device]));
+        let (icon, main, detail) = weather_label(snap, nerd);
+        lines.push(line("weather", &[icon, &main, &detail]));
+        lines
+    }
+
+    #[test]
+    fn card_labels_for_named_fixtures() {
+        let cases: [(&str, DashSnapshot, [&str; 16], [&str; 16]); 5] = [
+            ("idle laptop", idle_laptop(), [
+                "network: \u{f05a9} | wlan0 \u{00b7} 78%",
+                "battery: \u{f0079} | 64%",
+                "cpu: \u{f4bc} | 3%",
+                "memory: \u{f035b} | 2.0G | / 8.0G",
+                "swap: \u{f0552} | 0B / 0B",
+                "load: \u{f080} | 0.12 | 0.20 | 0.18",
+                "gpu: -",
+                "disk: \u{f01bc} | 120.0G | / 256.0G",
+                "volume: \u{f04c3} | Speakers | 35%",
+                "brightness: \u{f00e0} | 40%",
+                "media: \u{f0602} | \u{f040a} | \u{f0604} | Lo-fi Beats | ChilledCow",
+                "uptime: \u{f150e} | 2h 13m",
+                "temperature: \u{f050f} | 41°C",
+                "updates: \u{f0954} | Up to date",
+                "bluetooth: \u{f00af} | Disconnected",
+                "weather: \u{f0599} | Berlin | ⛅ +12°C",
+            ], [
+                "network: NET | wlan0 \u{00b7} 78%",
+                "battery: BAT | 64%",
+                "cpu: CPU | 3%",
+                "memory: RAM | 2.0G | / 8.0G",
+                "swap: SWP | 0B / 0B",
+                "load: LOAD | 0.12 | 0.20 | 0.18",
+                "gpu: -",
+                "disk: DSK | 120.0G | / 256.0G",
+                "volume: SPK | Speakers | 35%",
+                "brightness: BRT | 40%",
+                "media: ⏮ | ▶ | ⏭ | Lo-fi Beats | ChilledCow",
+                "uptime: UP | 2h 13m",
+                "temperature: TMP | 41°C",
+                "updates: UPD | Up to date",
+                "bluetooth: BT | Disconnected",
+                "weather: WX | Berlin | ⛅ +12°C",
+            ]),
+            ("loaded desktop", loaded_desktop(), [
+                "network: \u{f05a9} | eth0",
+                "battery: -",
+                "cpu: \u{f4bc} | 98%",
+                "memory: \u{f035b} | 28.0G | / 32.0G",
+                "swap: \u{f0552} | 3.0G / 8.0G",
+                "load: \u{f080} | 15.87 | 12.40 | 9.05",
+                "gpu: \u{f071b} | 99% | 83°C | 10.0G / 12.0G",
+                "disk: \u{f01bc} | 1.9T | / 2.0T",
+                "volume: \u{f02cb} | Headphones | 125%",
+                "brightness: \u{f00e0} | 50%",
+                "media: \u{f0602} | \u{f03e4} | \u{f0604} | Bohemian Rhapsody\u{2026} | Queen",
+                "uptime: \u{f150e} | 148h 02m",
+                "temperature: \u{f050f} | 89°C",
+                "updates: \u{f0954} | 42 updates",
+                "bluetooth: \u{f00af} | Connected | WH-1000XM4\u{2026}",
+                "weather: \u{f0599} | Oslo | 🌧 +4°C",
+            ], [
+                "network: NET | eth0",
+                "battery: -",
+                "cpu: CPU | 98%",
+                "memory: RAM | 28.0G | / 32.0G",
+                "swap: SWP | 3.0G / 8.0G",
+                "load: LOAD | 15.87 | 12.40 | 9.05",
+                "gpu: GPU | 99% | 83°C | 10.0G / 12.0G",
+                "disk: DSK | 1.9T | / 2.0T",
+                "volume: HP | Headphones | 125%",
+                "brightness: BRT | 50%",
+                "media: ⏮ | ⏸ | ⏭ | Bohemian Rhapsody\u{2026} | Queen",
+                "uptime: UP | 148h 02m",
+                "temperature: TMP | 89°C",
+                "updates: UPD | 42 updates",
+                "bluetooth: BT | Connected | WH-1000XM4\u{2026}",
+                "weather: WX | Oslo | 🌧 +4°C",
+            ]),
+            ("no battery", no_battery(), [
+                "network: \u{f05a9} | enp3s0",
+                "battery: -",
+                "cpu: \u{f4bc} | 12%",
+                "memory: \u{f035b} | 4.0G | / 16.0G",
+                "swap: \u{f0552} | 0B / 0B",
+                "load: \u{f080} | 0.50 | 0.40 | 0.30",
+                "gpu: \u{f071b} | 4%",
+                "disk: \u{f01bc} | 40.0G | / 500.0G",
+                "volume: \u{f075f} | Volume | 60%",
+                "brightness: \u{f00e0} | 50%",
+                "media: \u{f0602} | \u{f040a} | \u{f0604} | Nothing playing",
+                "uptime: \u{f150e} | 45m",
+                "temperature: -",
+                "updates: \u{f0954} | 3 updates",
+                "bluetooth: \u{f00af} | Connected | Connected",
+                "weather: \u{f0599} | Partly cloudy +12°C",
+            ], [
+                "network: NET | enp3s0",
+                "battery: -",
+                "cpu: CPU | 12%",
+                "memory: RAM | 4.0G | / 16.0G",
+                "swap: SWP | 0B / 0B",
+                "load: LOAD | 0.50 | 0.40 | 0.30",
+                "gpu: GPU | 4%",
+                "disk: DSK | 40.0G | / 500.0G",
+                "volume: M | Volume | 60%",
+                "brightness: BRT | 50%",
+                "media: ⏮ | ▶ | ⏭ | Nothing playing",
+                "uptime: UP | 45m",
+                "temperature: -",
+                "updates: UPD | 3 updates",
+                "bluetooth: BT | Connected | Connected",
+                "weather: WX | Partly cloudy +12°C",
+            ]),
+            ("offline", offline(), [
+                "network: \u{f05a9} | No network",
+                "battery: \u{f0079} | 22%",
+                "cpu: \u{f4bc} | 1%",
+                "memory: \u{f035b} | 1.0G | / 4.0G",
+                "swap: \u{f0552} | 0B / 0B",
+                "load: \u{f080} | 0.00 | 0.00 | 0.00",
+                "gpu: -",
+                "disk: \u{f01bc} | 512M | / 32.0G",
+                "volume: \u{f057e} | Volume | 0%",
+                "brightness: \u{f00e0} | 75%",
+                "media: \u{f0602} | \u{f040a} | \u{f0604} | Nothing playing",
+                "uptime: \u{f150e} | 5m",
+                "temperature: -",
+                "updates: \u{f0954} | Checking\u{2026}",
+                "bluetooth: \u{f00af} | Disconnected",
+                "weather: \u{f0599} | Fetching\u{2026}",
+            ], [
+                "network: NET | No network",
+                "battery: BAT | 22%",
+                "cpu: CPU | 1%",
+                "memory: RAM | 1.0G | / 4.0G",
+                "swap: SWP | 0B / 0B",
+                "load: LOAD | 0.00 | 0.00 | 0.00",
+                "gpu: -",
+                "disk: DSK | 512M | / 32.0G",
+                "volume: V | Volume | 0%",
+                "brightness: BRT | 75%",
+                "media: ⏮ | ▶ | ⏭ | Nothing playing",
+                "uptime: UP | 5m",
+                "temperature: -",
+                "updates: UPD | Checking\u{2026}",
+                "bluetooth: BT | Disconnected",
+                "weather: WX | Fetching\u{2026}",
+            ]),
+            ("charging at 100%", charging_full(), [
+                "network: \u{f05a9} | wlan0 \u{00b7} 100%",
+                "battery: \u{f0e7} | 100%",
+                "cpu: \u{f4bc} | 18%",
+                "memory: \u{f035b} | 6.0G | / 16.0G",
+                "swap: \u{f0552} | 512M / 4.0G",
+                "load: \u{f080} | 1.00 | 0.75 | 0.50",
+                "gpu: -",
+                "disk: \u{f01bc} | 200.0G | / 512.0G",
+                "volume: \u{f00b0} | Pixel Buds | 50%",
+                "brightness: \u{f00e0} | 100%",
+                "media: \u{f0602} | \u{f03e4} | \u{f0604} | Podcast",
+                "uptime: \u{f150e} | 26m",
+                "temperature: \u{f050f} | 35°C",
+                "updates: \u{f0954} | 1 updates",
+                "bluetooth: \u{f00af} | Connected | Pixel Buds",
+                "weather: \u{f0599} | Fetching\u{2026}",
+            ], [
+                "network: NET | wlan0 \u{00b7} 100%",
+                "battery: ⚡ | 100%",
+                "cpu: CPU | 18%",
+                "memory: RAM | 6.0G | / 16.0G",
+                "swap: SWP | 512M / 4.0G",
+                "load: LOAD | 1.00 | 0.75 | 0.50",
+                "gpu: -",
+                "disk: DSK | 200.0G | / 512.0G",
+                "volume: BT | Pixel Buds | 50%",
+                "brightness: BRT | 100%",
+                "media: ⏮ | ⏸ | ⏭ | Podcast",
+                "uptime: UP | 26m",
+                "temperature: TMP | 35°C",
+                "updates: UPD | 1 updates",
+                "bluetooth: BT | Connected | Pixel Buds",
+                "weather: WX | Fetching\u{2026}",
+            ]),
+        ];
+        for (name, snap, nerd, ascii) in cases {
+            assert_eq!(card_labels(&snap, true), strings(&nerd), "{name}, nerd icons");
+            assert_eq!(card_labels(&snap, false), strings(&ascii), "{name}, ascii icons");
+        }
+    }
+
+    #[test]
+    fn clock_label_follows_the_formats() {
+        use chrono::TimeZone;
+        let offset = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let now    = offset.with_ymd_and_hms(2024, 3, 9, 7, 5, 0).unwrap();
+        assert_eq!(clock_label(&now, "%H:%M", "%A, %d %B"), ("07:05".to_string(), "Saturday, 09 March".to_string()));
+        assert_eq!(clock_label(&now, "%-I:%M %p", "%Y-%m-%d %z"), ("7:05 AM".to_string(), "2024-03-09 +0200".to_string()));
+    }
+
+    #[tokio::test]
+    async fn metrics_bind_failure_becomes_a_notice() {
+        use iced::futures::StreamExt;
+
+        let taken = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr  = taken.local_addr().unwrap();
+        let msg   = Box::pin(metrics_server(&addr)).next().await;
+        let Some(Message::Notice(notice)) = msg else { panic!("expected a notice, got {msg:?}") };
+        assert!(notice.starts_with(&format!("Cannot serve metrics on {addr}: ")), "{notice}");
+
+        let mut dash = Dashboard::with_config(&DashConfig::default());
+        let _ = dash.update(Message::Notice(notice.clone()));
+        assert_eq!(dash.notice, Some(notice));
+        let _ = dash.update(Message::NoticeDismiss);
+        assert_eq!(dash.notice, None);
+    }
+
+    #[test]
+    fn slow_sources_carry_over_between_reads() {
+        let mouse = Peripheral { kind: "mouse".to_string(), name: "MX Master".to_string(), pct: 40 };
+        let mut dash = Dashboard::with_config(&DashConfig::default());
+        dash.apply_slice(Slice::Peripherals(vec![mouse.clone()]));
+        dash.apply_slice(Slice::Updates(3));
+        dash.apply_slice(Slice::Disk { used: 10, total: 40 });
+        dash.apply_slice(Slice::Weather("Oslo: +4°C".to_string()));
+
+        dash.merge_snapshot(DashSnapshot { cpu_pct: 12.0, ..Default::default() });
+        assert_eq!(dash.sys.cpu_pct, 12.0);
+        assert_eq!(dash.sys.peripherals, [mouse]);
+        assert_eq!(dash.sys.update_count, Some(3));
+        assert_eq!((dash.sys.disk_used, dash.sys.disk_total), (10, 40));
+        assert_eq!(dash.sys.weather_text, "Oslo: +4°C");
+
+        dash.apply_slice(Slice::Peripherals(Vec::new()));
+        dash.merge_snapshot(DashSnapshot::default());
+        assert!(dash.sys.peripherals.is_empty());
+        assert_eq!(dash.sys.update_count, Some(3));
+    }
+
+    #[test]
+    fn asleep_reads_keep_audio_and_media() {
+        let mut dash = Dashboard::with_config(&DashConfig::default());
+        dash.merge_snapshot(DashSnapshot {
+            volume:        Some(0.4),
+            media_title:   Some("Song".to_string()),
+            media_playing: true,
+            media_players: vec!["spotify".to_string()],
+            ..Default::default()
+        });
+
+        dash.merge_snapshot(DashSnapshot { cpu_pct: 3.0, asleep: true, ..Default::default() });
+        assert_eq!(dash.sys.cpu_pct, 3.0);
+        assert_eq!(dash.sys.volume, Some(0.4));
+        assert_eq!(dash.sys.media_title.as_deref(), Some("Song"));
+        assert!(dash.sys.media_playing);
+        assert_eq!(dash.sys.media_players, ["spotify"]);
+
+        dash.merge_snapshot(DashSnapshot::default());
+        assert_eq!(dash.sys.volume, None);
+        assert_eq!(dash.sys.media_title, None);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap_with_jitter() {
+        let base = Duration::from_secs(10);
+        let mut backoff = Backoff::new(base, Duration::from_secs(60));
+        for cap in [10, 20, 40, 60, 60] {
+            let cap = Duration::from_secs(cap);
+            let delay = backoff.next();
+            assert!(delay >= cap / 2 && delay <= cap, "{delay:?} outside {:?}..={cap:?}", cap / 2);
+        }
+        backoff.reset();
+        assert!(backoff.next() <= base);
+    }
+
+    #[test]
+    fn jitter_stays_in_range_and_varies() {
+        let samples: Vec<f64> = (0..32).map(|_| jitter()).collect();
+        assert!(samples.iter().all(|j| (0.0..1.0).contains(j)));
+        assert!(samples.iter().any(|&j| j != samples[0]));
+    }
+
+    #[test]
+    fn slow_sources_follow_the_config() {
+        let mut config = DashConfig::default();
+        let kinds = |config: &DashConfig| -> Vec<SlowSource> {
+            ReadOptions::from_config(config).slow_sources().into_iter().map(|(s, _)| s).collect()
+        };
+        assert_eq!(kinds(&config), [SlowSource::Disk, SlowSource::Updates]);
+
+        config.weather_location = "Oslo".to_string();
+        assert_eq!(kinds(&config), [
+            SlowSource::Disk,
+            SlowSource::Updates,
+            SlowSource::Weather("Oslo".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn fmt_pct_pads_to_three_figures() {
+        assert_eq!(fmt_pct(4.0, true), "\u{2007}\u{2007}4%");
+        assert_eq!(fmt_pct(42.0, true), "\u{2007}42%");
+        assert_eq!(fmt_pct(100.0, true), "100%");
+        assert_eq!(fmt_pct(99.6, true), "100%");
+        assert_eq!(fmt_pct(4.0, false), "4%");
+        assert_eq!(fmt_pct(100.0, false), "100%");
+        // Every padded value is the same width.
+        for value in [0.0, 4.0, 9.5, 42.0, 100.0] {
+            assert_eq!(fmt_pct(value, true).chars().count(), 4, "{value}");
+        }
+    }
+
+    fn card(kind: &str, col_span: u8) -> CardConfig {
+        CardConfig { kind: kind.to_string(), col_span, ..Default::default() }
+    }
+
+    fn drawer_group(first: &str) -> CardConfig {
+        CardConfig {
+            kind:     "group".to_string(),
+            drawer:   true,
+            children: vec![card(first, 1), card("disk", 1)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reload_keeps_open_drawers_and_the_caffeine_preset() {
+        let mut config = DashConfig::default();
+        config.dashboard.items    = vec![card("clock", 1), drawer_group("cpu"), drawer_group("memory")];
+        config.caffeine_minutes   = vec![30, 60, 120];
+        let mut dash = Dashboard::with_config(&config);
+        let _ = dash.update(Message::DrawerToggle(1));
+        let _ = dash.update(Message::CaffeineCycle);
+        let _ = dash.update(Message::CaffeineCycle);
+        assert_eq!(dash.caffeine_duration(), 120);
+
+        // A card added in front moves the open drawer along; a new duration
+        // list that still offers 120 minutes keeps it selected.
+        config.dashboard.items.insert(0, card("cpu", 1));
+        config.caffeine_minutes = vec![15, 120];
+        config.theme.accent     = "#ff0000".to_string();
+        dash.apply_config(config.clone());
+        assert_eq!(dash.caffeine_duration(), 120);
+        assert!(dash.drawers.get(&2).is_some_and(|d| d.open));
+        assert!(!dash.drawers.contains_key(&1));
+
+        // Editing the group itself closes its drawer; a dropped duration
+        // falls back to the first.
+        config.dashboard.items[2] = drawer_group("gpu");
+        config.caffeine_minutes   = vec![45];
+        dash.apply_config(config);
+        assert!(dash.drawers.is_empty());
+        assert_eq!(dash.caffeine_duration(), 45);
+    }
+
+    #[test]
+    fn carry_preset_follows_the_duration() {
+        assert_eq!(carry_preset(&[30, 60], &[60, 30], 1), 0);
+        assert_eq!(carry_preset(&[30, 60], &[10, 20], 1), 0);
+        assert_eq!(carry_preset(&[], &[10], 0), 0);
+    }
+
+    fn snap() -> DashSnapshot {
+        DashSnapshot {
+            cpu_pct:          7.4,
+            ram_used:         3 * 1_073_741_824 / 2,
+            ram_total:        16 * 1_073_741_824,
+            swap_used:        512 * 1_048_576,
+            swap_total:       2 * 1_073_741_824,
+            net_iface:        "wlan0".to_string(),
+            net_signal_pct:   Some(62),
+            battery_pct:      Some(81),
+            uptime_secs:      3 * 3600 + 5 * 60 + 59,
+            temp_celsius:     Some(47.4),
+            update_count:     Some(12),
+            ..Default::default()
+        }
+    }
+
+    fn size(bytes: u64) -> String {
+        format_bytes(bytes, ByteUnits::Iec)
+    }
+
+    #[test]
+    fn network_label_golden() {
+        let snap = snap();
+        assert_eq!(network_label(&snap, false, "pct"), ("NET", "wlan0 \u{00b7} 62%".to_string()));
+        assert_eq!(network_label(&snap, true, "bars"), ("\u{f05a9}", "wlan0 \u{2582}\u{2584}\u{2586}".to_string()));
+        assert_eq!(network_label(&snap, false, "off"), ("NET", "wlan0".to_string()));
+
+        let wired = DashSnapshot { net_iface: "eth0".to_string(), ..Default::default() };
+        assert_eq!(network_label(&wired, false, "bars"), ("NET", "eth0".to_string()));
+        let none = DashSnapshot::default();
+        assert_eq!(network_label(&none, false, "pct"), ("NET", "No network".to_string()));
+    }
+
+    #[test]
+    fn battery_label_golden() {
+        let mut snap = snap();
+        assert_eq!(battery_label(&snap, false, false), Some(("BAT", "81%".to_string())));
+        assert_eq!(battery_label(&snap, true, false), Some(("\u{f0079}", "81%".to_string())));
+        snap.battery_charging = true;
+        assert_eq!(battery_label(&snap, false, true), Some(("⚡", "\u{2007}81%".to_string())));
+        assert_eq!(battery_label(&snap, true, false), Some(("\u{f0e7}", "81%".to_string())));
+        assert_eq!(battery_label(&DashSnapshot::default(), false, false), None);
+    }
+
+    #[test]
+    fn prometheus_text_labels_network_and_battery() {
+        let mut dash = Dashboard::with_config(&DashConfig::default());
+        dash.host_info.host = "box".to_string();
+        dash.sys = snap();
+        let text = dash.prometheus_text();
+        assert!(text.contains("bar_battery_percent{host=\"box\"} 81\n"));
+        assert!(text.contains("bar_network_receive_bytes_per_second{host=\"box\",interface=\"wlan0\"} 0\n"));
+
+        dash.sys = DashSnapshot::default();
+        let text = dash.prometheus_text();
+        assert!(!text.contains("bar_battery_percent"));
+        assert!(!text.contains("interface="));
+    }
+
+    #[test]
+    fn network_and_battery_accessors() {
+        let mut snap = snap();
+        assert_eq!(snap.network(), Some(("wlan0", Some(62))));
+        assert_eq!(snap.battery(), Some((81, false)));
+        snap.battery_charging = true;
+        assert_eq!(snap.battery(), Some((81, true)));
+
+        let wired = DashSnapshot { net_iface: "eth0".to_string(), ..Default::default() };
+        assert_eq!(wired.network(), Some(("eth0", None)));
+        assert_eq!(DashSnapshot::default().network(), None);
+        assert_eq!(DashSnapshot { battery_charging: true, ..Default::default() }.battery(), None);
+    }
+
+    #[test]
+    fn usage_labels_golden() {
+        let snap = snap();
+        assert_eq!(cpu_label(&snap, false, false), ("CPU", "7%".to_string()));
+        assert_eq!(cpu_label(&snap, true, true), ("\u{f4bc}", "\u{2007}\u{2007}7%".to_string()));
+        assert_eq!(memory_label(&snap, false, size), ("RAM", "1.5G".to_string(), "/ 16.0G".to_string()));
+        assert_eq!(swap_label(&snap, false, size), ("SWP", "512M / 2.0G".to_string()));
+        assert_eq!(swap_label(&DashSnapshot::default(), true, size), ("\u{f0552}", "0B / 0B".to_string()));
+    }
+
+    #[test]
+    fn uptime_and_temperature_labels_golden() {
+        let snap = snap();
+        assert_eq!(uptime_label(&snap, false), ("UP", "3h 05m".to_string()));
+        assert_eq!(uptime_label(&DashSnapshot { uptime_secs: 59, ..Default::default() }, false), ("UP", "0m".to_string()));
+        assert_eq!(temperature_label(&snap, false, "c"), Some(("TMP", "47°C".to_string())));
+        assert_eq!(temperature_label(&snap, true, "f"), Some(("\u{f050f}", "117°F".to_string())));
+        assert_eq!(temperature_label(&DashSnapshot::default(), false, "c"), None);
+        assert_eq!(fmt_temp(-3.6, "c"), "-4°C");
+    }
+
+    #[test]
+    fn updates_label_and_peripheral_icons_golden() {
+        assert_eq!(updates_label(&snap(), false), ("UPD", "12 updates".to_string()));
+        let current = DashSnapshot { update_count: Some(0), ..Default::default() };
+        assert_eq!(updates_label(&current, true), ("\u{f0954}", "Up to date".to_string()));
+        assert_eq!(updates_label(&DashSnapshot::default(), false), ("UPD", "Checking\u{2026}".to_string()));
+
+        assert_eq!(peripheral_icon("mouse", false), "MSE");
+        assert_eq!(peripheral_icon("headphones", false), "HP");
+        assert_eq!(peripheral_icon("gaming-input", false), "PAD");
+        assert_eq!(peripheral_icon("keyboard", true), "\u{f030c}");
+    }
+
+    #[test]
+    fn item_span_uses_config_then_defaults() {
+        assert_eq!(item_span(&card("cpu", 1), 3), 1);
+        assert_eq!(item_span(&card("clock", 1), 3), 2);
+        assert_eq!(item_span(&card("cpu", 3), 3), 3);
+        assert_eq!(item_span(&card("cpu", 9), 2), 2);
+        assert_eq!(item_span(&card("media", 0), 1), 1);
+
+        let mut group = card("group", 1);
+        group.children = vec![card("cpu", 1), card("memory", 1), card("disk", 1)];
+        assert_eq!(item_span(&group, 4), 3);
+        assert_eq!(item_span(&group, 2), 2);
+        group.children.iter_mut().for_each(|c| c.enabled = false);
+        assert_eq!(item_span(&group, 4), 1);
     }
 }
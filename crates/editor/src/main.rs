@@ -4,47 +4,41 @@
 //! col/row spans, previews the grid in real-time, and saves back to disk.
 
 use bar_config::{
-    default_path, load as load_config,
+    default_path, is_json, load as load_config, load_raw,
     schema::{CardConfig, DashConfig},
 };
+use bar_theme::Color as ThemeColor;
 use iced::{
     widget::{
         button, column, container, pick_list, row, scrollable, text,
         rule,
     },
     Alignment, Background, Border, Color, Element, Length, Padding, Size,
-    Task,
+    Subscription, Task,
 };
+use std::time::Duration;
 
 // ── Constants ─────────────────────────────────────────────────────────────────
 
 const ALL_CARD_KINDS: &[&str] = &[
     "clock", "network", "battery", "cpu", "memory", "disk", "volume",
     "brightness", "media", "power", "uptime", "temperature", "updates",
-    "swap", "load", "gpu", "bluetooth", "weather",
+    "swap", "load", "gpu", "bluetooth", "weather", "self",
+    "host", "spacer", "debug", "metrics", "caffeine", "peripherals",
 ];
 
+/// How often the editor checks whether the dashboard is open.
+const DASHBOARD_POLL: Duration = Duration::from_secs(2);
+
 // ── Color helpers ─────────────────────────────────────────────────────────────
 
 fn hex_to_color(s: &str) -> Color {
-    let s = s.trim_start_matches('#');
-    if s.len() < 6 {
-        return Color::BLACK;
-    }
-    let r = u8::from_str_radix(&s[0..2], 16).unwrap_or(0) as f32 / 255.0;
-    let g = u8::from_str_radix(&s[2..4], 16).unwrap_or(0) as f32 / 255.0;
-    let b = u8::from_str_radix(&s[4..6], 16).unwrap_or(0) as f32 / 255.0;
-    Color::from_rgb(r, g, b)
+    ThemeColor::from_hex(s).unwrap_or(ThemeColor::BLACK).to_iced()
 }
 
 /// Slightly lighten a color component-wise.
 fn lighten(c: Color, amt: f32) -> Color {
-    Color {
-        r: (c.r + amt).min(1.0),
-        g: (c.g + amt).min(1.0),
-        b: (c.b + amt).min(1.0),
-        a: c.a,
-    }
+    ThemeColor::from(c).lighten(amt).to_iced()
 }
 
 // ── Card accent colors (Catppuccin Mocha palette) ─────────────────────────────
@@ -65,13 +59,41 @@ fn card_accent_color(kind: &str) -> Color {
         "media"               => Color::from_rgb(0.96, 0.54, 0.84),
         "uptime"              => Color::from_rgb(0.58, 0.89, 0.84),
         "updates"             => Color::from_rgb(0.98, 0.70, 0.53),
+        "caffeine"            => Color::from_rgb(0.92, 0.76, 0.60),
+        "peripherals"         => Color::from_rgb(0.65, 0.89, 0.63),
         "power"               => Color::from_rgb(0.96, 0.54, 0.67),
+        "self"                => Color::from_rgb(0.71, 0.75, 0.86),
+        "host"                => Color::from_rgb(0.58, 0.89, 0.84),
+        "spacer"              => Color::from_rgb(0.42, 0.44, 0.53),
+        "debug"               => Color::from_rgb(0.65, 0.68, 0.78),
+        "metrics"             => Color::from_rgb(0.79, 0.65, 0.97),
+        "group"               => Color::from_rgb(0.71, 0.75, 0.86),
         _                     => Color::from_rgb(0.79, 0.73, 0.62), // mauve/fallback
     }
 }
 
 // ── Card default span (mirrors dashboard logic) ───────────────────────────────
 
+/// Span of a card with no `col_span` set: groups span one column per child.
+fn card_col_span(card: &CardConfig) -> u8 {
+    if card.kind == "group" {
+        card.children.len().clamp(1, 4) as u8
+    } else {
+        default_col_span(&card.kind)
+    }
+}
+
+/// Card label for the list and preview; groups list their children, which
+/// are kept as-is when the layout is saved.
+fn card_label(card: &CardConfig) -> String {
+    if card.kind == "group" && !card.children.is_empty() {
+        let kinds: Vec<&str> = card.children.iter().map(|c| c.kind.as_str()).collect();
+        format!("group: {}", kinds.join(", "))
+    } else {
+        card.kind.clone()
+    }
+}
+
 fn default_col_span(kind: &str) -> u8 {
     match kind {
         "clock" | "media" | "power" | "load" => 2,
@@ -92,6 +114,8 @@ enum Message {
     MoveUp(usize),
     MoveDown(usize),
     RemoveCard(usize),
+    /// Switch a card on or off without removing it from the layout.
+    ToggleEnabled(usize),
     /// The pick_list selection changed to this card kind.
     AddCardPick(String),
     /// Confirm adding the currently-selected kind.
@@ -99,7 +123,27 @@ enum Message {
     ColumnsInc,
     ColumnsDec,
     Save,
-    SaveDone(Result<(), String>),
+    /// Save finished; carries the config that was written.
+    SaveDone(Result<Box<DashConfig>, String>),
+    /// Time to check whether the dashboard is open.
+    DashboardPoll,
+    DashboardProbed(DashboardStatus),
+    DashboardStart,
+    /// A dashboard started from here exited; `Err` if it failed to start
+    /// or exited with an error.
+    DashboardExited(Result<(), String>),
+    DashboardStop,
+    /// `kill` finished; `Err` says why the dashboard wasn't stopped.
+    DashboardStopped(Result<(), String>),
+}
+
+/// Whether the dashboard is open, judged by its layer surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DashboardStatus {
+    /// Not checked yet, or Hyprland couldn't be asked.
+    Unknown,
+    Stopped,
+    Running(u32),
 }
 
 // ── State ─────────────────────────────────────────────────────────────────────
@@ -107,18 +151,23 @@ enum Message {
 struct Editor {
     /// Full config (theme + dashboard sections).
     config:      DashConfig,
+    /// The config as last loaded or saved, to tell whether there are edits.
+    saved:       DashConfig,
     /// Currently-selected kind in the "Add card" pick_list.
     add_pick:    Option<String>,
     /// Status message shown after save.
     save_status: Option<String>,
+    dashboard:   DashboardStatus,
 }
 
 impl Default for Editor {
     fn default() -> Self {
         Self {
             config:      DashConfig::default(),
+            saved:       DashConfig::default(),
             add_pick:    Some("clock".to_string()),
             save_status: None,
+            dashboard:   DashboardStatus::Unknown,
         }
     }
 }
@@ -128,20 +177,40 @@ impl Default for Editor {
 fn boot() -> (Editor, Task<Message>) {
     let task = Task::perform(
         async {
-            let cfg = load_config(default_path()).unwrap_or_default();
+            // Edit the main file alone, so items and keys merged in from
+            // includes and conf.d/ aren't written back into it on save; the
+            // preview still uses the theme the dashboard actually ends up with.
+            let path    = default_path();
+            let mut cfg = load_raw(&path).unwrap_or_default();
+            if let Ok(full) = load_config(&path) {
+                cfg.theme = full.theme;
+            }
             Box::new(cfg)
         },
         Message::Loaded,
     );
-    (Editor::default(), task)
+    (Editor::default(), Task::batch([task, Task::done(Message::DashboardPoll)]))
 }
 
 // ── Update ────────────────────────────────────────────────────────────────────
 
 fn update(editor: &mut Editor, msg: Message) -> Task<Message> {
+    // Any edit makes the last save result out of date.
+    if !matches!(
+        msg,
+        Message::Save
+            | Message::SaveDone(_)
+            | Message::AddCardPick(_)
+            | Message::DashboardPoll
+            | Message::DashboardProbed(_)
+            | Message::DashboardExited(Ok(()))
+    ) {
+        editor.save_status = None;
+    }
     match msg {
         Message::Loaded(cfg) => {
             editor.config = *cfg;
+            editor.saved  = editor.config.clone();
             // Seed pick_list to first available kind not already in items
             editor.add_pick = pick_first_unused_kind(&editor.config.dashboard.items);
         }
@@ -184,6 +253,12 @@ fn update(editor: &mut Editor, msg: Message) -> Task<Message> {
             }
         }
 
+        Message::ToggleEnabled(i) => {
+            if let Some(c) = editor.config.dashboard.items.get_mut(i) {
+                c.enabled = !c.enabled;
+            }
+        }
+
         Message::AddCardPick(kind) => {
             editor.add_pick = Some(kind);
         }
@@ -193,7 +268,7 @@ fn update(editor: &mut Editor, msg: Message) -> Task<Message> {
                 editor.config.dashboard.items.push(CardConfig {
                     kind:     kind.clone(),
                     col_span,
-                    row_span: 1,
+                    ..Default::default()
                 });
             }
             editor.add_pick = pick_first_unused_kind(&editor.config.dashboard.items);
@@ -210,22 +285,98 @@ fn update(editor: &mut Editor, msg: Message) -> Task<Message> {
 
         Message::Save => {
             let path     = default_path();
+            let base     = editor.saved.clone();
             let cfg_snap = editor.config.clone();
             return Task::perform(
-                async move { save_config(cfg_snap, path).await },
+                async move { save_config(&base, &cfg_snap, path).await.map(|()| Box::new(cfg_snap)) },
                 Message::SaveDone,
             );
         }
         Message::SaveDone(result) => {
             editor.save_status = Some(match result {
-                Ok(()) => "Saved.".to_string(),
+                Ok(cfg) => {
+                    editor.saved = *cfg;
+                    "Saved.".to_string()
+                }
                 Err(e) => format!("Error: {e}"),
             });
         }
+
+        Message::DashboardPoll => {
+            let namespace = editor.saved.dashboard.layer_namespace().to_string();
+            return Task::perform(probe_dashboard(namespace), Message::DashboardProbed);
+        }
+        Message::DashboardProbed(status) => {
+            editor.dashboard = status;
+        }
+        Message::DashboardStart => {
+            return Task::perform(run_dashboard("bar-dashboard"), Message::DashboardExited);
+        }
+        Message::DashboardExited(result) => {
+            if let Err(e) = result {
+                editor.save_status = Some(e);
+            }
+            return Task::done(Message::DashboardPoll);
+        }
+        Message::DashboardStop => {
+            if let DashboardStatus::Running(pid) = editor.dashboard {
+                return Task::perform(stop_dashboard(pid), Message::DashboardStopped);
+            }
+        }
+        Message::DashboardStopped(result) => {
+            if let Err(e) = result {
+                editor.save_status = Some(format!("Cannot stop bar-dashboard: {e}"));
+            }
+            return Task::done(Message::DashboardPoll);
+        }
     }
     Task::none()
 }
 
+fn subscription(_editor: &Editor) -> Subscription<Message> {
+    iced::time::every(DASHBOARD_POLL).map(|_| Message::DashboardPoll)
+}
+
+/// Ask Hyprland whether a layer surface with the dashboard's namespace is
+/// mapped.  Uses the saved config's namespace, which is what a dashboard
+/// started from here would pick up.
+async fn probe_dashboard(namespace: String) -> DashboardStatus {
+    let probe = tokio::task::spawn_blocking(move || bar_core::hypr::layer_pid(&namespace)).await;
+    match probe {
+        Ok(Ok(Some(pid))) => DashboardStatus::Running(pid),
+        Ok(Ok(None))      => DashboardStatus::Stopped,
+        _                 => DashboardStatus::Unknown,
+    }
+}
+
+/// Start the dashboard and wait for it to exit, so it is reaped instead of
+/// lingering as a zombie.  Closing it or stopping it with a signal is a
+/// normal end; failing to start or a non-zero exit code is an `Err`.
+async fn run_dashboard(program: &'static str) -> Result<(), String> {
+    let mut child = tokio::process::Command::new(program)
+        .spawn()
+        .map_err(|e| format!("Cannot start {program}: {e}"))?;
+    let status = child.wait().await.map_err(|e| format!("{program}: {e}"))?;
+    match status.code() {
+        Some(code) if code != 0 => Err(format!("{program} exited with status {code}")),
+        _                       => Ok(()),
+    }
+}
+
+/// Ask the dashboard to quit with `kill` (SIGTERM).
+async fn stop_dashboard(pid: u32) -> Result<(), String> {
+    let out = tokio::process::Command::new("kill")
+        .arg(pid.to_string())
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if out.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+    Err(if stderr.is_empty() { out.status.to_string() } else { stderr })
+}
+
 /// Pick the first kind from ALL_CARD_KINDS not already in items, falling back
 /// to the first kind overall if all are present.
 fn pick_first_unused_kind(items: &[CardConfig]) -> Option<String> {
@@ -240,7 +391,9 @@ fn pick_first_unused_kind(items: &[CardConfig]) -> Option<String> {
 
 // ── Save logic ────────────────────────────────────────────────────────────────
 
-async fn save_config(cfg: DashConfig, path: std::path::PathBuf) -> Result<(), String> {
+/// Write `cfg` to `path`.  `base` is the config as last loaded or saved, so
+/// only what was edited since then is written into an existing file.
+async fn save_config(base: &DashConfig, cfg: &DashConfig, path: std::path::PathBuf) -> Result<(), String> {
     // Ensure parent directory exists
     if let Some(parent) = path.parent() {
         tokio::fs::create_dir_all(parent)
@@ -248,33 +401,143 @@ async fn save_config(cfg: DashConfig, path: std::path::PathBuf) -> Result<(), St
             .map_err(|e| format!("Cannot create config dir: {e}"))?;
     }
 
-    // Re-read existing raw TOML so we only touch the [dashboard] section.
-    // If the file doesn't exist yet, start from a full serialization.
-    let mut doc: toml::Value = if path.exists() {
+    if is_json(&path) {
+        return save_json(cfg, &path).await;
+    }
+
+    let fresh = to_document(cfg)?;
+
+    // Edit the existing file in place so its comments, key order and any keys
+    // this editor doesn't know about survive; only [dashboard] values that
+    // were actually edited are rewritten.  A new file gets the full config.
+    let doc = if path.exists() {
         let raw = tokio::fs::read_to_string(&path)
             .await
             .map_err(|e| format!("Cannot read config: {e}"))?;
-        toml::from_str(&raw).map_err(|e| format!("TOML parse error: {e}"))?
+        let mut doc: toml_edit::DocumentMut =
+            raw.parse().map_err(|e| format!("TOML parse error: {e}"))?;
+        let base = to_document(base)?;
+        match doc.get_mut("dashboard") {
+            Some(existing) => merge_item(existing, Some(&base["dashboard"]), &fresh["dashboard"]),
+            None => {
+                doc.insert("dashboard", fresh["dashboard"].clone());
+            }
+        }
+        doc
     } else {
-        toml::Value::Table(toml::map::Map::new())
+        fresh
     };
 
-    // Serialize the dashboard section
-    let dash_value = toml::Value::try_from(&cfg.dashboard)
-        .map_err(|e| format!("Serialize error: {e}"))?;
+    tokio::fs::write(&path, doc.to_string())
+        .await
+        .map_err(|e| format!("Cannot write config: {e}"))
+}
 
-    if let toml::Value::Table(ref mut root) = doc {
-        root.insert("dashboard".to_string(), dash_value);
-    }
+/// JSON flavour of [`save_config`]: JSON has no comments to keep, but the
+/// rest of the file, including unknown keys, is left as it was.
+async fn save_json(cfg: &DashConfig, path: &std::path::Path) -> Result<(), String> {
+    let mut doc = if path.exists() {
+        let raw = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format!("Cannot read config: {e}"))?;
+        serde_json::from_str(&raw).map_err(|e| format!("JSON parse error: {e}"))?
+    } else {
+        serde_json::to_value(cfg).map_err(|e| format!("Serialize error: {e}"))?
+    };
 
-    let serialized = toml::to_string_pretty(&doc)
-        .map_err(|e| format!("TOML serialize error: {e}"))?;
+    let dash = serde_json::to_value(&cfg.dashboard)
+        .map_err(|e| format!("Serialize error: {e}"))?;
+    match doc.as_object_mut() {
+        Some(root) => {
+            root.insert("dashboard".to_string(), dash);
+        }
+        None => return Err("JSON config is not an object".to_string()),
+    }
 
-    tokio::fs::write(&path, serialized)
+    let serialized = serde_json::to_string_pretty(&doc)
+        .map_err(|e| format!("JSON serialize error: {e}"))?;
+    tokio::fs::write(path, serialized + "\n")
         .await
         .map_err(|e| format!("Cannot write config: {e}"))
 }
 
+fn to_document(cfg: &DashConfig) -> Result<toml_edit::DocumentMut, String> {
+    toml::to_string(cfg)
+        .map_err(|e| format!("Serialize error: {e}"))?
+        .parse()
+        .map_err(|e| format!("Serialize error: {e}"))
+}
+
+/// Merge `fresh` into `existing`, where `base` is what `fresh` was edited
+/// from: anything unchanged since `base` is left alone, so defaults the file
+/// never spelled out stay implicit.  Tables merge key by key and the card
+/// list card by card; any other value is replaced only when it differs.
+/// Keys missing from `fresh` are kept, as are the comments and spacing
+/// around every value, changed or not.
+fn merge_item(existing: &mut toml_edit::Item, base: Option<&toml_edit::Item>, fresh: &toml_edit::Item) {
+    if base.is_some_and(|base| undecorated(base) == undecorated(fresh)) {
+        return;
+    }
+    if let (Some(old), Some(new)) = (existing.as_table_like_mut(), fresh.as_table_like()) {
+        merge_table(old, base.and_then(toml_edit::Item::as_table_like), new);
+        return;
+    }
+    if let (Some(old), Some(new)) = (existing.as_array_of_tables_mut(), fresh.as_array_of_tables()) {
+        let base = base.and_then(toml_edit::Item::as_array_of_tables);
+        while old.len() > new.len() {
+            old.remove(old.len() - 1);
+        }
+        for (i, card) in new.iter().enumerate() {
+            match old.get_mut(i) {
+                Some(slot) => {
+                    let was = base.and_then(|b| b.get(i)).map(|t| t as &dyn toml_edit::TableLike);
+                    merge_table(slot, was, card);
+                }
+                None => old.push(card.clone()),
+            }
+        }
+        return;
+    }
+    if undecorated(existing) == undecorated(fresh) {
+        return;
+    }
+    let decor = existing.as_value().map(|v| v.decor().clone());
+    *existing = fresh.clone();
+    if let (Some(decor), Some(value)) = (decor, existing.as_value_mut()) {
+        *value.decor_mut() = decor;
+    }
+}
+
+/// [`merge_item`] for the keys of one table.
+fn merge_table(
+    old:  &mut dyn toml_edit::TableLike,
+    base: Option<&dyn toml_edit::TableLike>,
+    new:  &dyn toml_edit::TableLike,
+) {
+    for (key, value) in new.iter() {
+        let was = base.and_then(|b| b.get(key));
+        match old.get_mut(key) {
+            Some(slot) => merge_item(slot, was, value),
+            None if was.is_some_and(|was| undecorated(was) == undecorated(value)) => {}
+            None => {
+                old.insert(key, value.clone());
+            }
+        }
+    }
+}
+
+/// An item's TOML text without the whitespace and comments around it.  It's
+/// rendered inside a document so tables include their sub-tables.
+fn undecorated(item: &toml_edit::Item) -> String {
+    let mut item = item.clone();
+    if let Some(value) = item.as_value_mut() {
+        value.decor_mut().clear();
+    }
+    let mut doc = toml_edit::DocumentMut::new();
+    doc.insert("item", item);
+    doc.to_string()
+}
+
 // ── View ──────────────────────────────────────────────────────────────────────
 
 fn view(editor: &Editor) -> Element<'_, Message> {
@@ -308,34 +571,22 @@ fn view(editor: &Editor) -> Element<'_, Message> {
     );
 
     // ── Save button / status ──────────────────────────────────────────────────
+    let dirty = editor.config != editor.saved;
     let save_label = editor
         .save_status
         .as_deref()
         .unwrap_or("Save");
 
-    let save_btn = button(
-        text(save_label).size(fsize).color(fg),
-    )
-    .padding(Padding { top: 6.0, right: 16.0, bottom: 6.0, left: 16.0 })
-    .style(move |_: &iced::Theme, status| {
-        let bg_color = match status {
-            button::Status::Hovered | button::Status::Pressed => {
-                Color { a: 0.25, ..accent }
-            }
-            _ => Color { a: 0.15, ..accent },
-        };
-        button::Style {
-            background: Some(Background::Color(bg_color)),
-            border: Border {
-                radius: 6.0.into(),
-                color: Color { a: 0.40, ..accent },
-                width: 1.0,
-            },
-            text_color: fg,
-            ..Default::default()
-        }
-    })
-    .on_press(Message::Save);
+    let save_btn = top_bar_btn(save_label, fg, accent, fsize, dirty.then_some(Message::Save));
+
+    // ── Dashboard status / start-stop ─────────────────────────────────────────
+    let green = Color::from_rgb(0.67, 0.88, 0.63);
+    let (status_label, status_col, btn_label, btn_msg) = match editor.dashboard {
+        DashboardStatus::Running(_) => ("\u{25cf} Dashboard open",   green, "Stop",  Message::DashboardStop),
+        DashboardStatus::Stopped    => ("\u{25cb} Dashboard closed", muted, "Start", Message::DashboardStart),
+        DashboardStatus::Unknown    => ("Dashboard status unknown",  muted, "Start", Message::DashboardStart),
+    };
+    let dashboard_btn = top_bar_btn(btn_label, fg, accent, fsize, Some(btn_msg));
 
     // ── Top bar ───────────────────────────────────────────────────────────────
     let top_bar = container(
@@ -344,6 +595,8 @@ fn view(editor: &Editor) -> Element<'_, Message> {
                 .size(fsize + 2.0)
                 .color(Color { a: 0.85, ..fg }),
             iced::widget::Space::new().width(Length::Fill),
+            text(status_label).size(fsize - 2.0).color(status_col),
+            dashboard_btn,
             save_btn,
         ]
         .align_y(Alignment::Center)
@@ -413,7 +666,9 @@ fn view_card_list<'a>(
         let is_first = i == 0;
         let is_last  = i + 1 == items.len();
 
-        let kind_accent = card_accent_color(&card.kind);
+        // Disabled cards keep their row but are drawn greyed out
+        let kind_accent = if card.enabled { card_accent_color(&card.kind) } else { muted };
+        let label_col   = if card.enabled { fg } else { muted };
 
         // Kind label with colored dot
         let dot = container(iced::widget::Space::new())
@@ -427,7 +682,7 @@ fn view_card_list<'a>(
 
         let kind_label = row![
             dot,
-            text(card.kind.clone()).size(fsize - 1.0).color(fg),
+            text(card_label(card)).size(fsize - 1.0).color(label_col),
         ]
         .spacing(6.0)
         .align_y(Alignment::Center);
@@ -452,7 +707,9 @@ fn view_card_list<'a>(
             Message::RowSpanInc(i),
         );
 
-        // Reorder + remove buttons
+        // Enable toggle, reorder + remove buttons
+        let on_btn = small_btn(if card.enabled { "\u{25cf}" } else { "\u{25cb}" }, fg, accent, fsize,
+            Some(Message::ToggleEnabled(i)));
         let up_btn = small_btn("↑", fg, accent, fsize,
             if is_first { None } else { Some(Message::MoveUp(i)) });
         let dn_btn = small_btn("↓", fg, accent, fsize,
@@ -465,6 +722,8 @@ fn view_card_list<'a>(
             iced::widget::Space::new().width(Length::Fixed(8.0)),
             row_span_row,
             iced::widget::Space::new().width(Length::Fill),
+            on_btn,
+            iced::widget::Space::new().width(Length::Fixed(4.0)),
             up_btn,
             dn_btn,
             iced::widget::Space::new().width(Length::Fixed(4.0)),
@@ -568,6 +827,7 @@ fn view_card_list<'a>(
 
 // ── Right panel: live preview ─────────────────────────────────────────────────
 
+#[allow(clippy::too_many_arguments)]
 fn view_preview<'a>(
     editor:        &'a Editor,
     bg:            Color,
@@ -581,6 +841,7 @@ fn view_preview<'a>(
     let dash    = &editor.config.dashboard;
     let cols    = dash.columns.clamp(2, 4) as usize;
     let gap     = 12.0f32;
+    let rtl     = dash.direction.eq_ignore_ascii_case("rtl");
 
     // Card base size in preview
     let base_w  = 140.0f32;
@@ -591,29 +852,24 @@ fn view_preview<'a>(
     let mut row_items: Vec<Element<'_, Message>> = Vec::new();
     let mut row_span  = 0usize;
 
-    for card in &dash.items {
+    for card in dash.items.iter().filter(|c| c.enabled) {
         let kind  = card.kind.as_str();
         let col_s = if card.col_span > 1 {
             (card.col_span as usize).min(cols)
         } else {
-            (default_col_span(kind) as usize).min(cols)
+            (card_col_span(card) as usize).min(cols)
         };
         let row_s = (card.row_span as usize).max(1);
 
         if row_span + col_s > cols && !row_items.is_empty() {
-            grid_rows.push(
-                iced::widget::Row::from_vec(std::mem::take(&mut row_items))
-                    .spacing(gap)
-                    .align_y(Alignment::Start)
-                    .into(),
-            );
+            grid_rows.push(preview_row(std::mem::take(&mut row_items), gap, rtl));
             row_span = 0;
         }
 
         let card_w = base_w * col_s as f32 + gap * (col_s - 1) as f32;
         let card_h = base_h * row_s as f32 + gap * (row_s - 1) as f32;
         let kind_accent = card_accent_color(kind);
-        let kind_owned  = kind.to_string();
+        let kind_owned  = card_label(card);
 
         let card_elem = container(
             column![
@@ -655,27 +911,17 @@ fn view_preview<'a>(
         row_span += col_s;
 
         if row_span >= cols {
-            grid_rows.push(
-                iced::widget::Row::from_vec(std::mem::take(&mut row_items))
-                    .spacing(gap)
-                    .align_y(Alignment::Start)
-                    .into(),
-            );
+            grid_rows.push(preview_row(std::mem::take(&mut row_items), gap, rtl));
             row_span = 0;
         }
     }
     if !row_items.is_empty() {
-        grid_rows.push(
-            iced::widget::Row::from_vec(row_items)
-                .spacing(gap)
-                .align_y(Alignment::Start)
-                .into(),
-        );
+        grid_rows.push(preview_row(row_items, gap, rtl));
     }
 
     let grid = iced::widget::Column::from_vec(grid_rows)
         .spacing(gap)
-        .align_x(Alignment::Start);
+        .align_x(if rtl { Alignment::End } else { Alignment::Start });
 
     // ── Columns stepper ───────────────────────────────────────────────────────
     let cols_stepper = row![
@@ -730,6 +976,51 @@ fn view_preview<'a>(
 
 // ── Shared widget helpers ─────────────────────────────────────────────────────
 
+/// A labelled button for the top bar (Save, Start/Stop).
+/// One preview row, mirrored for `direction = "rtl"` as the dashboard does.
+fn preview_row(mut cards: Vec<Element<'_, Message>>, gap: f32, rtl: bool) -> Element<'_, Message> {
+    if rtl {
+        cards.reverse();
+    }
+    iced::widget::Row::from_vec(cards)
+        .spacing(gap)
+        .align_y(Alignment::Start)
+        .into()
+}
+
+fn top_bar_btn<'a>(
+    label:    &'a str,
+    fg:       Color,
+    accent:   Color,
+    fsize:    f32,
+    on_press: Option<Message>,
+) -> Element<'a, Message> {
+    button(
+        text(label).size(fsize).color(fg),
+    )
+    .padding(Padding { top: 6.0, right: 16.0, bottom: 6.0, left: 16.0 })
+    .style(move |_: &iced::Theme, status| {
+        let bg_color = match status {
+            button::Status::Hovered | button::Status::Pressed => {
+                Color { a: 0.25, ..accent }
+            }
+            _ => Color { a: 0.15, ..accent },
+        };
+        button::Style {
+            background: Some(Background::Color(bg_color)),
+            border: Border {
+                radius: 6.0.into(),
+                color: Color { a: 0.40, ..accent },
+                width: 1.0,
+            },
+            text_color: fg,
+            ..Default::default()
+        }
+    })
+    .on_press_maybe(on_press)
+    .into()
+}
+
 /// A small (30px wide) icon button.
 fn small_btn<'a>(
     label:   &'static str,
@@ -818,7 +1109,102 @@ fn span_stepper<'a>(
 
 fn main() -> iced::Result {
     iced::application(boot, update, view)
+        .subscription(subscription)
         .title("bar-editor")
         .window_size(Size::new(1100.0, 650.0))
         .run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r##"# Laptop dashboard
+[theme]
+accent = "#ff0000"  # red
+my_theme_note = "not a real key"
+
+[dashboard]
+# two columns felt cramped
+columns = 3
+future_option = true
+
+[[dashboard.items]]
+kind = "clock"  # always first
+
+[[dashboard.items]]
+kind = "cpu"
+
+[plugins.weather]
+provider = "wttr"
+"##;
+
+    async fn round_trip(edit: impl FnOnce(&mut DashConfig)) -> String {
+        let dir  = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bar.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+
+        let base    = bar_config::load_raw(&path).unwrap();
+        let mut cfg = base.clone();
+        edit(&mut cfg);
+        save_config(&base, &cfg, path.clone()).await.unwrap();
+        std::fs::read_to_string(&path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn unchanged_save_keeps_the_file_as_is() {
+        assert_eq!(round_trip(|_| {}).await, CONFIG);
+    }
+
+    #[tokio::test]
+    async fn save_keeps_comments_order_and_unknown_keys() {
+        let saved = round_trip(|cfg| cfg.dashboard.columns = 4).await;
+        assert_eq!(saved, CONFIG.replace("columns = 3", "columns = 4"));
+    }
+
+    #[tokio::test]
+    async fn edited_card_changes_only_its_key() {
+        let saved = round_trip(|cfg| cfg.dashboard.items[1].col_span = 2).await;
+        assert_eq!(saved, CONFIG.replace("kind = \"cpu\"\n", "kind = \"cpu\"\ncol_span = 2\n"));
+    }
+
+    #[tokio::test]
+    async fn removed_and_added_cards_keep_the_rest_of_the_file() {
+        let saved = round_trip(|cfg| {
+            cfg.dashboard.items.pop();
+            cfg.dashboard.items.push(CardConfig { kind: "disk".to_string(), ..Default::default() });
+        })
+        .await;
+        assert!(saved.starts_with("# Laptop dashboard\n[theme]\naccent = \"#ff0000\"  # red\n"));
+        assert!(saved.contains("# two columns felt cramped\ncolumns = 3\nfuture_option = true\n"));
+        assert!(saved.contains("kind = \"clock\"  # always first\n"));
+        assert!(!saved.contains("kind = \"cpu\""));
+        let disk  = saved.find("kind = \"disk\"").unwrap();
+        let extra = saved.find("[plugins.weather]\nprovider = \"wttr\"\n").unwrap();
+        assert!(disk < extra);
+    }
+
+    #[test]
+    fn merge_item_keeps_decor_of_changed_values() {
+        let mut existing: toml_edit::DocumentMut = "a = 1  # one\nb = 2\n".parse().unwrap();
+        let base: toml_edit::DocumentMut = "a = 1\nc = 3\n".parse().unwrap();
+        let fresh: toml_edit::DocumentMut = "a = 5\nc = 3\nd = 4\n".parse().unwrap();
+        merge_item(existing.as_item_mut(), Some(base.as_item()), fresh.as_item());
+        assert_eq!(existing.to_string(), "a = 5  # one\nb = 2\nd = 4\n");
+    }
+
+    #[tokio::test]
+    async fn dashboard_runs_are_waited_for_and_failures_reported() {
+        assert_eq!(run_dashboard("true").await, Ok(()));
+        assert_eq!(run_dashboard("false").await, Err("false exited with status 1".to_string()));
+        let err = run_dashboard("bar-dashboard-missing").await.unwrap_err();
+        assert!(err.starts_with("Cannot start bar-dashboard-missing: "), "{err}");
+    }
+
+    #[tokio::test]
+    async fn stopping_a_missing_dashboard_reports_why() {
+        // No process has this PID, so `kill` fails (or isn't there at all).
+        let err = stop_dashboard(u32::MAX - 1).await.unwrap_err();
+        assert!(!err.is_empty());
+    }
+}
@@ -11,11 +11,19 @@ impl Color {
     pub const DARK:        Self = Self { r: 0.118, g: 0.118, b: 0.180, a: 1.0 }; // #1e1e2e
     pub const WHITE:       Self = Self { r: 0.804, g: 0.839, b: 0.957, a: 1.0 }; // #cdd6f4
     pub const PURPLE:      Self = Self { r: 0.796, g: 0.651, b: 0.969, a: 1.0 }; // #cba6f7
+    pub const YELLOW:      Self = Self { r: 0.976, g: 0.886, b: 0.686, a: 1.0 }; // #f9e2af
+    pub const RED:         Self = Self { r: 0.953, g: 0.545, b: 0.659, a: 1.0 }; // #f38ba8
     pub const TRANSPARENT: Self = Self { r: 0.0,   g: 0.0,   b: 0.0,   a: 0.0 };
+    pub const BLACK:       Self = Self { r: 0.0,   g: 0.0,   b: 0.0,   a: 1.0 };
 
     /// Parse a CSS-style hex color string (`#RRGGBB` or `#RRGGBBAA`).
     pub fn from_hex(hex: &str) -> Option<Self> {
         let hex = hex.trim_start_matches('#');
+        // Also keeps the byte slicing below on char boundaries: a stray
+        // non-ASCII character would otherwise panic instead of failing the parse.
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
 
         let byte = |s: &str| -> Option<u8> { u8::from_str_radix(s, 16).ok() };
 
@@ -49,4 +57,90 @@ impl Color {
         self.a = alpha.clamp(0.0, 1.0);
         self
     }
+
+    /// Linear blend towards `other`: `t = 0.0` is `self`, `t = 1.0` is `other`.
+    /// All four channels are interpolated; `t` is clamped to `[0.0, 1.0]`.
+    #[must_use]
+    pub fn mix(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+            a: lerp(self.a, other.a),
+        }
+    }
+
+    /// Add `amount` to each RGB channel (saturating at 1.0); alpha is kept.
+    #[must_use]
+    pub fn lighten(self, amount: f32) -> Self {
+        Self {
+            r: (self.r + amount).clamp(0.0, 1.0),
+            g: (self.g + amount).clamp(0.0, 1.0),
+            b: (self.b + amount).clamp(0.0, 1.0),
+            a: self.a,
+        }
+    }
+
+    /// Subtract `amount` from each RGB channel (saturating at 0.0); alpha is kept.
+    #[must_use]
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+}
+
+impl From<iced::Color> for Color {
+    fn from(c: iced::Color) -> Self {
+        Self { r: c.r, g: c.g, b: c.b, a: c.a }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_six_and_eight_digit_hex() {
+        assert_eq!(Color::from_hex("#000000"), Some(Color::BLACK));
+        assert_eq!(Color::from_hex("ffffff00"), Some(Color { r: 1.0, g: 1.0, b: 1.0, a: 0.0 }));
+        assert_eq!(Color::from_hex("#fff"), None);
+        assert_eq!(Color::from_hex(""), None);
+    }
+
+    #[test]
+    fn multi_byte_input_is_rejected_without_panicking() {
+        // Byte lengths of 6 and 8 would reach the slicing if not caught first.
+        for hex in ["#ééé", "#€€", "#1e1e2€", "#東京", "🎨🎨", "#12345é", "#ffffff🎨"] {
+            assert_eq!(Color::from_hex(hex), None, "{hex}");
+        }
+    }
+
+    fn close(a: Color, b: Color) -> bool {
+        [(a.r, b.r), (a.g, b.g), (a.b, b.b), (a.a, b.a)]
+            .iter()
+            .all(|(x, y)| (x - y).abs() < 1e-6)
+    }
+
+    #[test]
+    fn mix_interpolates_every_channel() {
+        let from = Color { r: 0.0, g: 0.2, b: 1.0, a: 0.0 };
+        let to   = Color { r: 1.0, g: 0.6, b: 0.0, a: 1.0 };
+        assert!(close(from.mix(to, 0.0), from));
+        assert!(close(from.mix(to, 1.0), to));
+        assert!(close(from.mix(to, 0.5), Color { r: 0.5, g: 0.4, b: 0.5, a: 0.5 }));
+        // Out-of-range `t` is clamped rather than extrapolated.
+        assert!(close(from.mix(to, -1.0), from));
+        assert!(close(from.mix(to, 2.0), to));
+    }
+
+    #[test]
+    fn lighten_and_darken_clamp_and_keep_alpha() {
+        let c = Color { r: 0.9, g: 0.5, b: 0.05, a: 0.4 };
+        assert!(close(c.lighten(0.2), Color { r: 1.0, g: 0.7, b: 0.25, a: 0.4 }));
+        assert!(close(c.darken(0.1), Color { r: 0.8, g: 0.4, b: 0.0, a: 0.4 }));
+        assert!(close(c.lighten(5.0), Color { r: 1.0, g: 1.0, b: 1.0, a: 0.4 }));
+        assert!(close(c.darken(5.0), Color { r: 0.0, g: 0.0, b: 0.0, a: 0.4 }));
+        assert!(close(c.lighten(0.0), c));
+    }
 }
@@ -4,7 +4,12 @@ pub mod style;
 pub use colors::Color;
 pub use style::{BarStyle, WidgetStyle};
 
-use bar_config::ThemeConfig;
+use bar_config::{ReduceMotion, ThemeConfig};
+use std::sync::OnceLock;
+
+/// The desktop's reduced-motion preference, once [`detect_reduced_motion`]
+/// has asked for it.
+static DESKTOP_REDUCED_MOTION: OnceLock<bool> = OnceLock::new();
 
 /// Compiled theme derived from [`ThemeConfig`].
 #[derive(Debug, Clone)]
@@ -12,6 +17,8 @@ pub struct Theme {
     pub background:    Color,
     pub foreground:    Color,
     pub accent:        Color,
+    pub warning:       Color,
+    pub critical:      Color,
     pub font_name:     String,
     pub font_size:     f32,
     pub border_radius: f32,
@@ -25,6 +32,8 @@ pub struct Theme {
     pub widget_border_width: u32,
     /// `strftime` format string for the clock time display.
     pub clock_format:  String,
+    /// `"c"` or `"f"`, lowercased.
+    pub temperature_unit: String,
     /// `strftime` format string for the clock date display.
     pub date_format:   String,
     /// When `true`, widgets render Nerd Font glyphs.  `false` → ASCII labels.
@@ -35,10 +44,52 @@ pub struct Theme {
     pub widget_pad_y:  u16,
     /// When `true`, the clock widget appends seconds to the time display.
     pub clock_show_seconds: bool,
+    /// Date under the time on the clock card; `false` = on hover only.
+    pub clock_show_date: bool,
     /// Battery percent at which the battery icon switches to a low-power glyph.
     pub battery_warn_percent: u8,
+    /// Disk usage percentages for the warning and critical colours.
+    pub disk_warn_percent: u8,
+    pub disk_critical_percent: u8,
     /// Visual style for power menu buttons: `"icon_label"`, `"icon_only"`, or `"pill"`.
     pub power_button_style: String,
+    /// Character limit for the media card's track title.
+    pub media_max_chars: usize,
+    /// Character limit for the Bluetooth card's device name.
+    pub bluetooth_max_chars: usize,
+    /// Host card contents: `"user_host"` or `"host"`.
+    pub host_show: String,
+    /// Volume slider step in percent (at least 1).
+    pub volume_step: u8,
+    /// Brightness slider step in percent (at least 1).
+    pub brightness_step: u8,
+    /// Accelerate quick successive wheel notches on volume/brightness cards.
+    pub scroll_acceleration: bool,
+    /// Volume ceiling as a fraction, clamped to `0.1..=1.5`.
+    pub volume_max: f32,
+    /// `"off"`, `"pct"` or `"bars"`, lowercased.
+    pub network_signal: String,
+    /// Show the active audio output on the volume card.
+    pub volume_show_port: bool,
+    /// Pad percentages to a fixed width.
+    pub pad_numbers: bool,
+    /// `"iec"` or `"si"`, lowercased.
+    pub byte_units: String,
+    /// `'.'` or `','`.
+    pub decimal_separator: char,
+    /// `"bytes"` or `"bits"`, lowercased.
+    pub rate_units: String,
+    /// Card drop shadow; `None` when disabled.
+    pub shadow: Option<Color>,
+    /// Shadow blur radius in pixels.
+    pub shadow_blur: f32,
+    /// Downward shadow offset in pixels.
+    pub shadow_offset: f32,
+    /// Animations are off, from `reduce_motion` (with `"auto"` resolved).
+    pub reduce_motion: bool,
+    /// `reduce_motion = "auto"`: follow the desktop's preference, which is
+    /// `false` until [`detect_reduced_motion`] has run.
+    pub reduce_motion_auto: bool,
 }
 
 impl Theme {
@@ -48,6 +99,8 @@ impl Theme {
             background:    Color::from_hex(&cfg.background).unwrap_or(Color::DARK),
             foreground:    Color::from_hex(&cfg.foreground).unwrap_or(Color::WHITE),
             accent:        Color::from_hex(&cfg.accent).unwrap_or(Color::PURPLE),
+            warning:       Color::from_hex(&cfg.warning).unwrap_or(Color::YELLOW),
+            critical:      Color::from_hex(&cfg.critical).unwrap_or(Color::RED),
             font_name:     cfg.font.clone(),
             font_size:     cfg.font_size,
             border_radius: cfg.border_radius,
@@ -62,14 +115,83 @@ impl Theme {
                 .unwrap_or(Color::DARK),
             widget_border_width: cfg.widget_border_width,
             clock_format:        cfg.clock_format.clone(),
+            temperature_unit:    cfg.temperature_unit.to_lowercase(),
             date_format:         cfg.date_format.clone(),
             use_nerd_icons:      cfg.icon_style.to_lowercase() != "ascii",
             widget_pad_x:        cfg.widget_padding_x,
             widget_pad_y:        cfg.widget_padding_y,
             clock_show_seconds:  cfg.clock_show_seconds,
+            clock_show_date:     cfg.clock_show_date,
             battery_warn_percent: cfg.battery_warn_percent,
+            disk_warn_percent:   cfg.disk_warn_percent.min(100),
+            disk_critical_percent: cfg.disk_critical_percent.min(100),
             power_button_style:  cfg.power_button_style.clone(),
+            media_max_chars:     cfg.media_max_chars,
+            bluetooth_max_chars: cfg.bluetooth_max_chars,
+            host_show:           cfg.host_show.clone(),
+            volume_step:         cfg.volume_step.max(1),
+            brightness_step:     cfg.brightness_step.max(1),
+            scroll_acceleration: cfg.scroll_acceleration,
+            volume_max:          cfg.volume_max.clamp(0.1, 1.5),
+            network_signal:      cfg.network_signal.to_lowercase(),
+            volume_show_port:    cfg.volume_show_port,
+            pad_numbers:         cfg.pad_numbers,
+            byte_units:          cfg.byte_units.to_lowercase(),
+            decimal_separator:   if cfg.decimal_separator.trim() == "," { ',' } else { '.' },
+            rate_units:          cfg.rate_units.to_lowercase(),
+            shadow: if cfg.shadow {
+                Some(Color::from_hex(&cfg.shadow_color).unwrap_or(Color::TRANSPARENT.with_alpha(0.5)))
+            } else {
+                None
+            },
+            shadow_blur:         cfg.shadow_blur.max(0.0),
+            shadow_offset:       cfg.shadow_offset,
+            reduce_motion: match &cfg.reduce_motion {
+                ReduceMotion::Set(on) => *on,
+                ReduceMotion::Mode(mode) if mode.eq_ignore_ascii_case("auto") => {
+                    DESKTOP_REDUCED_MOTION.get().copied().unwrap_or(false)
+                }
+                ReduceMotion::Mode(_) => false,
+            },
+            reduce_motion_auto: matches!(
+                &cfg.reduce_motion,
+                ReduceMotion::Mode(mode) if mode.eq_ignore_ascii_case("auto")
+            ),
+        }
+    }
+
+    /// Whether to animate.  Every animation checks this, and with it off
+    /// jumps straight to the end state.
+    pub fn animate(&self) -> bool {
+        !self.reduce_motion
+    }
+}
+
+/// The desktop's reduced-motion preference, asked for once and cached for
+/// `"auto"` themes built afterwards.  Runs `busctl`, so call it off the UI
+/// thread.
+pub fn detect_reduced_motion() -> bool {
+    *DESKTOP_REDUCED_MOTION.get_or_init(desktop_reduced_motion)
+}
+
+/// Read the preference from the settings portal with `busctl` (reply
+/// `v u 1` = reduce).  `false` when there's no portal, it doesn't know the
+/// key, or it doesn't answer within a second.
+fn desktop_reduced_motion() -> bool {
+    let out = std::process::Command::new("busctl")
+        .args([
+            "--user", "--timeout=1", "call",
+            "org.freedesktop.portal.Desktop",
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Settings",
+            "ReadOne", "ss", "org.freedesktop.appearance", "reduced-motion",
+        ])
+        .output();
+    match out {
+        Ok(out) if out.status.success() => {
+            String::from_utf8_lossy(&out.stdout).split_whitespace().last() == Some("1")
         }
+        _ => false,
     }
 }
 